@@ -81,6 +81,7 @@ const state = {
     isBoosting: false,
     countdown: 3,
     roundActive: false,
+    speedScale: 1,         // Overtime speed multiplier published by the server
     cameraShake: 0,
     turnLeft: false,
     turnRight: false,
@@ -373,6 +374,8 @@ function createPlayerEntity(p) {
 
     // Apply Tron-style turning setting from config
     playerEntity.setTronStyleTurning(PHYSICS_CONFIG.tronStyleTurning);
+    // Bot reaction delay is published by the server (ai::bot_reaction_delay)
+    playerEntity.network.reactionDelay = p.reaction_delay ?? 0;
 
     // Create TrailEntity
     const trailEntity = new TrailEntity(playerId, {
@@ -431,6 +434,7 @@ function updatePlayerEntity(p) {
     entity.setPosition(p.x, p.z);
     entity.setDirection(p.dir_x || 0, p.dir_z || -1);
     entity.setSpeed(p.speed || localConfig.baseSpeed);
+    entity.network.reactionDelay = p.reaction_delay ?? entity.network.reactionDelay;
     
     // Update state component
     if (p.alive === false) {
@@ -497,6 +501,17 @@ function handleGameState(gs) {
     state.roundActive = gs.round_active !== undefined ? gs.round_active :
                         (gs.roundActive !== undefined ? gs.roundActive : false);
 
+    // Follow sudden-death overtime: speed up in step with the server's limits
+    const speedScale = typeof gs.speed_scale === 'number' ? gs.speed_scale :
+                       (typeof gs.speedScale === 'number' ? gs.speedScale : 1);
+    if (speedScale !== state.speedScale) {
+        const me = state.players[myPlayerId];
+        if (me && me.state.alive && state.speedScale > 0) {
+            me.physics.setSpeed(me.physics.speed * speedScale / state.speedScale);
+        }
+        state.speedScale = speedScale;
+    }
+
     // Update countdown UI
     const countdownEl = document.getElementById('countdown');
     if (countdownEl) {
@@ -1176,10 +1191,19 @@ function checkCollisions(allSegments, dt = 0.016) {
  */
 function updateAI(allSegments) {
     const players = Object.values(state.players);
+    const now = performance.now() / 1000;
 
     players.forEach(entity => {
         if (!entity || !entity.network.isAi || !entity.state.alive || !state.roundActive) return;
 
+        // Easier bots keep their last decision until their reaction delay passes
+        const lastDecision = entity.network.lastDecisionAt ?? -Infinity;
+        if (now - lastDecision < entity.network.reactionDelay) {
+            sendStateSync(entity);
+            return;
+        }
+        entity.network.lastDecisionAt = now;
+
         const pos = entity.getPosition();
         const dir = entity.getDirection();
 
@@ -0,0 +1,328 @@
+//! Server-side AI helpers for Cyber Cycles
+//!
+//! This module provides decision making for AI-controlled bikes:
+//! - A coarse danger field cached once per tick
+//! - Turn decisions sampled from the field in O(1)
+//! - Detection of boxed-in bikes
+//! - Reaction delays by difficulty level
+
+use crate::physics::collision::{
+    distance_to_segment_struct, segments_intersect, time_to_wall, PlayerState, Segment,
+};
+
+/// Seconds before a wall impact at which bots start turning
+pub const AI_WALL_REACTION_TIME: f32 = 0.5;
+
+/// Highest bot difficulty level
+pub const MAX_BOT_DIFFICULTY: u8 = 5;
+
+/// Difficulty given to new AI bikes
+pub const DEFAULT_BOT_DIFFICULTY: u8 = 3;
+
+/// Reaction delay of a difficulty-0 bot (seconds)
+pub const SLOWEST_BOT_REACTION: f32 = 0.4;
+
+/// Reaction delay of a max-difficulty bot (seconds)
+pub const FASTEST_BOT_REACTION: f32 = 0.05;
+
+/// How long a bot waits between turn decisions
+///
+/// Falls linearly from `SLOWEST_BOT_REACTION` at difficulty 0 to
+/// `FASTEST_BOT_REACTION` at `MAX_BOT_DIFFICULTY`; higher levels clamp.
+pub fn bot_reaction_delay(difficulty: u8) -> f32 {
+    let t = difficulty.min(MAX_BOT_DIFFICULTY) as f32 / MAX_BOT_DIFFICULTY as f32;
+    SLOWEST_BOT_REACTION - (SLOWEST_BOT_REACTION - FASTEST_BOT_REACTION) * t
+}
+
+/// Coarse grid of approximate distances to the nearest hazard
+///
+/// Each cell stores the distance from its center to the closest trail
+/// segment or arena wall. Building is O(cells * segments); sampling is O(1),
+/// so bots can share one field per tick. One tick of staleness is fine for AI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DangerField {
+    /// Half-size of the arena covered by the field
+    pub arena_size: f32,
+    /// Width of each square cell (units)
+    pub cell_size: f32,
+    /// Number of cells along each axis
+    pub cells_per_side: usize,
+    /// Row-major distances, indexed by `row * cells_per_side + col`
+    pub distances: Vec<f32>,
+}
+
+impl DangerField {
+    /// Approximate distance to the nearest hazard at a point
+    ///
+    /// Points outside the arena sample the nearest edge cell.
+    pub fn sample(&self, x: f32, z: f32) -> f32 {
+        let col = self.cell_index(x);
+        let row = self.cell_index(z);
+        self.distances[row * self.cells_per_side + col]
+    }
+
+    fn cell_index(&self, v: f32) -> usize {
+        let idx = ((v + self.arena_size) / self.cell_size).floor();
+        (idx.max(0.0) as usize).min(self.cells_per_side - 1)
+    }
+}
+
+/// Builds a danger field for the current trails
+///
+/// # Arguments
+/// * `segments` - All trail segments in the arena
+/// * `arena_size` - Half-size of the arena
+/// * `cell_size` - Width of each grid cell (clamped to at least 1 unit)
+///
+/// # Returns
+/// DangerField covering the whole arena
+pub fn build_danger_field(segments: &[Segment], arena_size: f32, cell_size: f32) -> DangerField {
+    let cell_size = cell_size.max(1.0);
+    let cells_per_side = ((arena_size * 2.0) / cell_size).ceil().max(1.0) as usize;
+    let mut distances = Vec::with_capacity(cells_per_side * cells_per_side);
+
+    for row in 0..cells_per_side {
+        for col in 0..cells_per_side {
+            let cx = -arena_size + (col as f32 + 0.5) * cell_size;
+            let cz = -arena_size + (row as f32 + 0.5) * cell_size;
+
+            // Walls are hazards too
+            let mut nearest = arena_size - cx.abs().max(cz.abs());
+            for segment in segments {
+                nearest = nearest.min(distance_to_segment_struct(cx, cz, segment));
+            }
+            distances.push(nearest.max(0.0));
+        }
+    }
+
+    DangerField { arena_size, cell_size, cells_per_side, distances }
+}
+
+/// Decides whether an AI bike should turn
+///
+/// Samples the danger field straight ahead; if it is closer than
+/// `clearance`, or a wall is less than `AI_WALL_REACTION_TIME` away at the
+/// current speed, the bike turns toward whichever diagonal probe is safer.
+///
+/// # Arguments
+/// * `player` - AI bike state
+/// * `field` - Danger field for this tick
+/// * `speed` - Current speed (units/sec)
+/// * `lookahead` - Distance ahead to probe (units)
+/// * `clearance` - Minimum acceptable distance to a hazard
+///
+/// # Returns
+/// Tuple of (turn_left, turn_right)
+pub fn ai_decide_turn(
+    player: &PlayerState,
+    field: &DangerField,
+    speed: f32,
+    lookahead: f32,
+    clearance: f32,
+) -> (bool, bool) {
+    let ahead = probe(player, field, lookahead, 0.0);
+    let wall_soon = time_to_wall(player, speed, field.arena_size)
+        .is_some_and(|t| t < AI_WALL_REACTION_TIME);
+    if ahead >= clearance && !wall_soon {
+        return (false, false);
+    }
+
+    // Positive angles turn left, matching PhysicsConfig::calculate_turn_angle
+    let left = probe(player, field, lookahead, std::f32::consts::FRAC_PI_4);
+    let right = probe(player, field, lookahead, -std::f32::consts::FRAC_PI_4);
+
+    if left >= right {
+        (true, false)
+    } else {
+        (false, true)
+    }
+}
+
+/// `ai_decide_turn`, re-decided only once the bot's reaction delay passes
+///
+/// Between decisions the bot keeps doing whatever it last decided, so
+/// easier bots notice hazards later.
+///
+/// # Arguments
+/// * `player` - AI bike state
+/// * `field` - Danger field for this tick
+/// * `speed` - Current speed (units/sec)
+/// * `lookahead` - Distance ahead to probe (units)
+/// * `clearance` - Minimum acceptable distance to a hazard
+/// * `since_decision` - Seconds (ticks times tick length) since the
+///   bot last decided
+/// * `difficulty` - Bot difficulty level
+///
+/// # Returns
+/// Some((turn_left, turn_right)) when it is time to decide, None to keep
+/// the previous decision
+pub fn ai_decide_turn_delayed(
+    player: &PlayerState,
+    field: &DangerField,
+    speed: f32,
+    lookahead: f32,
+    clearance: f32,
+    since_decision: f32,
+    difficulty: u8,
+) -> Option<(bool, bool)> {
+    if since_decision < bot_reaction_delay(difficulty) {
+        return None;
+    }
+    Some(ai_decide_turn(player, field, speed, lookahead, clearance))
+}
+
+/// Detects whether a bike is boxed in with no safe exit
+///
+/// Casts `fan` rays spread evenly across the forward half-plane (from hard
+/// left to hard right; a single ray points straight ahead) and reports a
+/// trap only if every ray hits a segment within `lookahead`. Arena walls
+/// count only if the caller includes them in `segments`.
+///
+/// # Arguments
+/// * `player` - Bike state
+/// * `segments` - Trail (and optionally wall) segments
+/// * `lookahead` - Ray length (units)
+/// * `fan` - Number of rays to cast
+///
+/// # Returns
+/// True if every ray is blocked
+pub fn is_trapped(player: &PlayerState, segments: &[Segment], lookahead: f32, fan: usize) -> bool {
+    if fan == 0 || segments.is_empty() {
+        return false;
+    }
+
+    (0..fan).all(|i| {
+        let angle = if fan == 1 {
+            0.0
+        } else {
+            std::f32::consts::FRAC_PI_2 - std::f32::consts::PI * i as f32 / (fan - 1) as f32
+        };
+        let (sin, cos) = angle.sin_cos();
+        let dir_x = player.dir_x * cos - player.dir_z * sin;
+        let dir_z = player.dir_x * sin + player.dir_z * cos;
+        let ray = Segment::new(
+            player.x,
+            player.z,
+            player.x + dir_x * lookahead,
+            player.z + dir_z * lookahead,
+        );
+        segments.iter().any(|s| segments_intersect(&ray, s))
+    })
+}
+
+/// Samples the field at `distance` along the heading rotated by `angle`
+fn probe(player: &PlayerState, field: &DangerField, distance: f32, angle: f32) -> f32 {
+    let (sin, cos) = angle.sin_cos();
+    let dir_x = player.dir_x * cos - player.dir_z * sin;
+    let dir_z = player.dir_x * sin + player.dir_z * cos;
+    field.sample(player.x + dir_x * distance, player.z + dir_z * distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall_ahead() -> Vec<Segment> {
+        vec![Segment::new(20.0, -30.0, 20.0, 30.0)]
+    }
+
+    #[test]
+    fn test_danger_field_dimensions() {
+        let field = build_danger_field(&[], 100.0, 10.0);
+        assert_eq!(field.cells_per_side, 20);
+        assert_eq!(field.distances.len(), 400);
+    }
+
+    #[test]
+    fn test_danger_field_low_near_trail() {
+        let field = build_danger_field(&wall_ahead(), 100.0, 2.0);
+        assert!(field.sample(20.0, 0.0) < 2.0);
+    }
+
+    #[test]
+    fn test_danger_field_high_far_away() {
+        let field = build_danger_field(&wall_ahead(), 100.0, 2.0);
+        assert!(field.sample(-40.0, 0.0) > 50.0);
+    }
+
+    #[test]
+    fn test_danger_field_sample_outside_arena() {
+        let field = build_danger_field(&[], 100.0, 10.0);
+        // Clamped to the edge cell rather than panicking
+        assert!(field.sample(500.0, -500.0) < 10.0);
+    }
+
+    #[test]
+    fn test_ai_goes_straight_in_open_field() {
+        let field = build_danger_field(&[], 100.0, 2.0);
+        let bot = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+
+        assert_eq!(ai_decide_turn(&bot, &field, 40.0, 10.0, 5.0), (false, false));
+    }
+
+    #[test]
+    fn test_ai_turns_away_from_trail() {
+        // Trail ahead that extends further on the left (+z) side
+        let segments = [Segment::new(15.0, -5.0, 15.0, 60.0)];
+        let field = build_danger_field(&segments, 100.0, 1.0);
+        let bot = PlayerState::new("p1".to_string(), 5.0, 0.0, 1.0, 0.0, true);
+
+        assert_eq!(ai_decide_turn(&bot, &field, 40.0, 10.0, 5.0), (false, true));
+    }
+
+    #[test]
+    fn test_ai_turns_before_wall_at_speed() {
+        let field = build_danger_field(&[], 100.0, 2.0);
+        // 30 units from the wall: clear of the probe, but under 0.5s at 70 u/s
+        let bot = PlayerState::new("p1".to_string(), 70.0, 0.0, 1.0, 0.0, true);
+
+        assert_eq!(ai_decide_turn(&bot, &field, 20.0, 10.0, 5.0), (false, false));
+        assert_ne!(ai_decide_turn(&bot, &field, 70.0, 10.0, 5.0), (false, false));
+    }
+
+    #[test]
+    fn test_higher_difficulty_reacts_faster() {
+        let delays: Vec<f32> = (0..=MAX_BOT_DIFFICULTY).map(bot_reaction_delay).collect();
+        assert!(delays.windows(2).all(|w| w[1] < w[0]));
+        assert_eq!(delays[0], SLOWEST_BOT_REACTION);
+        assert!((bot_reaction_delay(MAX_BOT_DIFFICULTY) - FASTEST_BOT_REACTION).abs() < 1e-6);
+        // Out-of-range levels clamp to the hardest
+        assert_eq!(bot_reaction_delay(200), bot_reaction_delay(MAX_BOT_DIFFICULTY));
+    }
+
+    #[test]
+    fn test_delayed_decision_waits_for_reaction() {
+        let field = build_danger_field(&wall_ahead(), 100.0, 1.0);
+        let bot = PlayerState::new("p1".to_string(), 15.0, 0.0, 1.0, 0.0, true);
+        let ticks = |n: u32| n as f32 / 60.0;
+
+        // Ten 60Hz ticks is too soon for the easiest bot, not the hardest
+        assert_eq!(ai_decide_turn_delayed(&bot, &field, 40.0, 10.0, 5.0, ticks(10), 0), None);
+        assert!(ai_decide_turn_delayed(&bot, &field, 40.0, 10.0, 5.0, ticks(10), MAX_BOT_DIFFICULTY).is_some());
+        assert_eq!(
+            ai_decide_turn_delayed(&bot, &field, 40.0, 10.0, 5.0, ticks(24), 0),
+            Some(ai_decide_turn(&bot, &field, 40.0, 10.0, 5.0)),
+        );
+    }
+
+    #[test]
+    fn test_not_trapped_in_open_field() {
+        let bot = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        assert!(!is_trapped(&bot, &wall_ahead(), 30.0, 7));
+    }
+
+    #[test]
+    fn test_trapped_in_three_sided_pocket() {
+        // Pocket open behind the bike (-x), closed ahead and on both sides
+        let pocket = [
+            Segment::new(10.0, -5.0, 10.0, 5.0),
+            Segment::new(-5.0, 5.0, 10.0, 5.0),
+            Segment::new(-5.0, -5.0, 10.0, -5.0),
+        ];
+        let bot = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+
+        assert!(is_trapped(&bot, &pocket, 30.0, 7));
+        // Too short a lookahead can't see the far wall
+        assert!(!is_trapped(&bot, &pocket, 4.0, 7));
+    }
+}
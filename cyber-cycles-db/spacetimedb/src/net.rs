@@ -0,0 +1,669 @@
+//! Network encoding helpers for Cyber Cycles
+//!
+//! This module provides compact wire formats for player state:
+//! - 16-bit fixed point positions relative to arena bounds
+//! - Direction packed into a single 16-bit angle
+//! - Distance-based trail decimation for spectators
+//! - Deterministic state hashing for desync detection
+//! - Parsing of (optionally timestamped) trail points
+//! - Deterministic trail point serialization
+//! - Smoothed speedometer values for the HUD
+
+use std::f32::consts::TAU;
+
+use crate::physics::collision::{PlayerState, Segment};
+use crate::physics::{PhysicsError, PhysicsResult};
+use crate::Vec2;
+
+/// Largest value of a quantized 16-bit component
+const QUANT_MAX: f32 = u16::MAX as f32;
+
+/// Compact player state for network transmission
+///
+/// Positions and direction are stored as 16-bit values instead of
+/// 32-bit floats, roughly halving the per-tick payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedPlayerState {
+    /// Quantized X position
+    pub x: u16,
+    /// Quantized Z position
+    pub z: u16,
+    /// Packed heading angle
+    pub dir: u16,
+}
+
+impl PackedPlayerState {
+    /// Pack a player state for transmission
+    ///
+    /// # Arguments
+    /// * `player` - Player state to pack
+    /// * `arena_size` - Half-size of the arena
+    pub fn pack(player: &PlayerState, arena_size: f32) -> Self {
+        Self {
+            x: quantize_pos(player.x, arena_size),
+            z: quantize_pos(player.z, arena_size),
+            dir: quantize_dir(player.dir_x, player.dir_z),
+        }
+    }
+
+    /// Unpack into (x, z, dir_x, dir_z)
+    ///
+    /// # Arguments
+    /// * `arena_size` - Half-size of the arena used when packing
+    pub fn unpack(&self, arena_size: f32) -> (f32, f32, f32, f32) {
+        let (dir_x, dir_z) = dequantize_dir(self.dir);
+        (
+            dequantize_pos(self.x, arena_size),
+            dequantize_pos(self.z, arena_size),
+            dir_x,
+            dir_z,
+        )
+    }
+}
+
+/// Quantizes a coordinate into 16-bit fixed point
+///
+/// The range `-arena_size..=arena_size` is mapped onto `0..=u16::MAX`.
+/// Values outside the arena are clamped to the nearest bound.
+///
+/// # Arguments
+/// * `v` - Coordinate to quantize
+/// * `arena_size` - Half-size of the arena
+///
+/// # Returns
+/// Quantized coordinate
+pub fn quantize_pos(v: f32, arena_size: f32) -> u16 {
+    if arena_size <= 0.0 {
+        return 0;
+    }
+
+    let normalized = ((v + arena_size) / (arena_size * 2.0)).clamp(0.0, 1.0);
+    (normalized * QUANT_MAX).round() as u16
+}
+
+/// Restores a coordinate from 16-bit fixed point
+///
+/// # Arguments
+/// * `q` - Quantized coordinate
+/// * `arena_size` - Half-size of the arena
+///
+/// # Returns
+/// Decoded coordinate
+pub fn dequantize_pos(q: u16, arena_size: f32) -> f32 {
+    (q as f32 / QUANT_MAX) * arena_size * 2.0 - arena_size
+}
+
+/// Packs a direction vector into a single 16-bit angle
+///
+/// # Arguments
+/// * `dir_x`, `dir_z` - Direction vector (need not be normalized)
+///
+/// # Returns
+/// Packed angle
+pub fn quantize_dir(dir_x: f32, dir_z: f32) -> u16 {
+    let mut angle = dir_z.atan2(dir_x);
+    if angle < 0.0 {
+        angle += TAU;
+    }
+
+    // Wrap so that an angle of exactly TAU maps back to zero
+    ((angle / TAU) * (QUANT_MAX + 1.0)).round() as u32 as u16
+}
+
+/// Restores a unit direction vector from a packed angle
+///
+/// # Arguments
+/// * `q` - Packed angle
+///
+/// # Returns
+/// Unit direction as (dir_x, dir_z)
+pub fn dequantize_dir(q: u16) -> (f32, f32) {
+    let angle = (q as f32 / (QUANT_MAX + 1.0)) * TAU;
+    (angle.cos(), angle.sin())
+}
+
+/// FNV-1a 64-bit offset basis
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Position resolution used when hashing (units)
+pub const HASH_POS_RESOLUTION: f32 = 0.1;
+
+/// Hashes player states for client/server desync detection
+///
+/// Players are hashed in id order, so input order doesn't matter. For each
+/// player the id bytes, position (rounded to `HASH_POS_RESOLUTION` as
+/// little-endian i32), packed heading (little-endian u16) and alive flag are
+/// fed through FNV-1a. Clients must hash the same fields the same way.
+///
+/// # Arguments
+/// * `players` - Player states to hash
+///
+/// # Returns
+/// 64-bit state hash
+pub fn state_hash(players: &[PlayerState]) -> u64 {
+    let mut sorted: Vec<&PlayerState> = players.iter().collect();
+    sorted.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut hash = FNV_OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+
+    for p in sorted {
+        feed(p.id.as_bytes());
+        feed(&((p.x / HASH_POS_RESOLUTION).round() as i32).to_le_bytes());
+        feed(&((p.z / HASH_POS_RESOLUTION).round() as i32).to_le_bytes());
+        feed(&quantize_dir(p.dir_x, p.dir_z).to_le_bytes());
+        feed(&[p.alive as u8]);
+    }
+
+    hash
+}
+
+/// Simplifies a trail polyline based on distance from a viewer
+///
+/// Points within `near` are always kept. Between `near` and `far`, a point
+/// is kept only once it is at least `distance - near` units from the last
+/// kept point, so resolution falls off with distance. Runs of points beyond
+/// `far` collapse to their first and last point. The trail's endpoints are
+/// always kept.
+///
+/// # Arguments
+/// * `points` - Trail points in order
+/// * `viewer` - Viewer position
+/// * `near` - Distance within which full detail is kept
+/// * `far` - Distance beyond which runs collapse to straight lines
+///
+/// # Returns
+/// Decimated trail points, preserving order
+pub fn decimate_for_lod(points: &[Vec2], viewer: &Vec2, near: f32, far: f32) -> Vec<Vec2> {
+    if points.len() <= 2 {
+        return points.to_vec();
+    }
+
+    let distance = |p: &Vec2| ((p.x - viewer.x).powi(2) + (p.z - viewer.z).powi(2)).sqrt();
+    let last = points.len() - 1;
+    let mut kept: Vec<Vec2> = vec![points[0].clone()];
+
+    for i in 1..last {
+        let point = &points[i];
+        let d = distance(point);
+
+        let keep = if d <= near {
+            true
+        } else if d >= far {
+            // Keep the boundaries of a far run so its shape stays anchored
+            distance(&points[i - 1]) < far || distance(&points[i + 1]) < far
+        } else {
+            let prev = &kept[kept.len() - 1];
+            let spacing = ((point.x - prev.x).powi(2) + (point.z - prev.z).powi(2)).sqrt();
+            spacing >= d - near
+        };
+
+        if keep {
+            kept.push(point.clone());
+        }
+    }
+
+    kept.push(points[last].clone());
+    kept
+}
+
+/// Decimal places used when serializing trail coordinates
+pub const TRAIL_DECIMALS: usize = 2;
+
+/// Formats a trail coordinate with exactly `TRAIL_DECIMALS` decimals
+///
+/// Output is identical on every platform, so serialized trails hash the
+/// same everywhere. Negative zero is written as positive zero.
+pub fn format_point(v: f32) -> String {
+    let s = format!("{:.*}", TRAIL_DECIMALS, v);
+    if s.starts_with('-') && s[1..].bytes().all(|b| b == b'0' || b == b'.') {
+        s[1..].to_string()
+    } else {
+        s
+    }
+}
+
+/// Parses a coordinate written by `format_point`
+///
+/// # Returns
+/// The value, or None if `s` isn't a finite number
+pub fn parse_point(s: &str) -> Option<f32> {
+    s.trim().parse::<f32>().ok().filter(|v| v.is_finite())
+}
+
+/// Serializes trail points in the format read by `parse_trail_points`
+///
+/// Coordinates go through `format_point`; the tick index is written only
+/// when present.
+pub fn format_trail_points(points: &[TrailPoint]) -> String {
+    let body: Vec<String> = points.iter()
+        .map(|p| match p.t {
+            Some(t) => format!(
+                "{{\"x\":{},\"z\":{},\"t\":{}}}", format_point(p.x), format_point(p.z), t,
+            ),
+            None => format!("{{\"x\":{},\"z\":{}}}", format_point(p.x), format_point(p.z)),
+        })
+        .collect();
+    format!("[{}]", body.join(","))
+}
+
+/// A trail point as stored in `turn_points_json`
+///
+/// Legacy points are `{"x":..,"z":..}`; timed points add the tick index they
+/// were laid on as `"t"`, so replays can reconstruct timing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrailPoint {
+    /// X position
+    pub x: f32,
+    /// Z position
+    pub z: f32,
+    /// Tick index the point was laid on, if recorded
+    pub t: Option<u32>,
+}
+
+/// Parses a trail point array in either the legacy or timed format
+///
+/// Both formats may be mixed in one array. Unknown keys are ignored.
+///
+/// # Arguments
+/// * `json` - JSON array of points, e.g. `[{"x":1,"z":2,"t":30}]`
+///
+/// # Returns
+/// * `Ok(points)` in order
+/// * `Err(PhysicsError::InvalidState)` if the array is malformed
+pub fn parse_trail_points(json: &str) -> PhysicsResult<Vec<TrailPoint>> {
+    let invalid = |msg: &str| PhysicsError::InvalidState(format!("trail points: {}", msg));
+
+    let body = json.trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or_else(|| invalid("expected a JSON array"))?;
+
+    let mut points = Vec::new();
+    let mut rest = body.trim();
+
+    while !rest.is_empty() {
+        let open = rest.strip_prefix('{').ok_or_else(|| invalid("expected '{'"))?;
+        let close = open.find('}').ok_or_else(|| invalid("unterminated object"))?;
+        let (object, tail) = (&open[..close], &open[close + 1..]);
+
+        let (mut x, mut z, mut t) = (None, None, None);
+        for field in object.split(',').filter(|f| !f.trim().is_empty()) {
+            let (key, value) = field.split_once(':').ok_or_else(|| invalid("expected key:value"))?;
+            let value = value.trim();
+            match key.trim().trim_matches('"') {
+                "x" => x = Some(parse_point(value).ok_or_else(|| invalid("bad x"))?),
+                "z" => z = Some(parse_point(value).ok_or_else(|| invalid("bad z"))?),
+                "t" => t = Some(value.parse::<u32>().map_err(|_| invalid("bad t"))?),
+                _ => {}
+            }
+        }
+
+        points.push(TrailPoint {
+            x: x.ok_or_else(|| invalid("missing x"))?,
+            z: z.ok_or_else(|| invalid("missing z"))?,
+            t,
+        });
+
+        let tail = tail.trim_start();
+        rest = match tail.strip_prefix(',') {
+            Some(next) => next.trim_start(),
+            None if tail.is_empty() => tail,
+            None => return Err(invalid("expected ','")),
+        };
+    }
+
+    Ok(points)
+}
+
+/// Tick rate behind the `"t"` index of timed trail points
+pub const TRAIL_TICKS_PER_SECOND: f32 = 60.0;
+
+/// Parses a trail point array into segments between consecutive points
+///
+/// Geometry is identical for both formats. A segment's `laid_at` is the
+/// round time its end point was laid (falling back to its start point);
+/// untimed segments count as laid at the start of the round.
+///
+/// # Arguments
+/// * `json` - JSON array of points (see `parse_trail_points`)
+///
+/// # Returns
+/// * `Ok(segments)`, empty for fewer than two points
+/// * `Err(PhysicsError::InvalidState)` if the array is malformed
+pub fn parse_trail_segments(json: &str) -> PhysicsResult<Vec<Segment>> {
+    let points = parse_trail_points(json)?;
+
+    Ok(points.windows(2)
+        .map(|w| {
+            let laid_at = w[1].t.or(w[0].t).map_or(0.0, |t| t as f32 / TRAIL_TICKS_PER_SECOND);
+            Segment::new(w[0].x, w[0].z, w[1].x, w[1].z).with_laid_at(laid_at)
+        })
+        .collect())
+}
+
+/// Smooths a speed reading for display
+///
+/// One step of exponential smoothing from the last displayed value toward
+/// the real speed. Presentation only: physics always uses the real speed.
+///
+/// # Arguments
+/// * `current` - Speed shown last frame
+/// * `target` - Actual speed
+/// * `smoothing` - Fraction of the gap closed per step (clamped to 0.0..=1.0;
+///   1.0 snaps to the target)
+///
+/// # Returns
+/// Speed to show this frame
+pub fn display_speed(current: f32, target: f32, smoothing: f32) -> f32 {
+    current + (target - current) * smoothing.clamp(0.0, 1.0)
+}
+
+/// Trail segments keyed by owner id, as taken by `check_all_collisions`
+pub type TrailsByOwner = Vec<(String, Vec<Segment>)>;
+
+/// Parses every player's trail for a batch collision check
+///
+/// A corrupt trail must not abort the whole tick, so failures are collected
+/// instead of propagated. The owner's trail is simply left out; the owner is
+/// still checked against everyone else's trails.
+///
+/// # Arguments
+/// * `raw` - (owner_id, trail JSON) pairs
+///
+/// # Returns
+/// Tuple of (parsed trails keyed by owner, parse errors keyed by owner)
+pub fn parse_all_trails<'a, I>(raw: I) -> (TrailsByOwner, Vec<(String, PhysicsError)>)
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let mut trails = Vec::new();
+    let mut errors = Vec::new();
+
+    for (owner, json) in raw {
+        match parse_trail_segments(json) {
+            Ok(segments) => trails.push((owner.to_string(), segments)),
+            Err(e) => errors.push((owner.to_string(), e)),
+        }
+    }
+    (trails, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::collision::{check_all_collisions, COLLISION_CONFIG};
+
+    const ARENA: f32 = 200.0;
+
+    #[test]
+    fn test_quantize_pos_bounds() {
+        assert_eq!(quantize_pos(-ARENA, ARENA), 0);
+        assert_eq!(quantize_pos(ARENA, ARENA), u16::MAX);
+    }
+
+    #[test]
+    fn test_quantize_pos_clamps_outside_arena() {
+        assert_eq!(quantize_pos(-500.0, ARENA), 0);
+        assert_eq!(quantize_pos(500.0, ARENA), u16::MAX);
+    }
+
+    #[test]
+    fn test_quantize_pos_round_trip_error() {
+        let mut v = -ARENA;
+        while v <= ARENA {
+            let decoded = dequantize_pos(quantize_pos(v, ARENA), ARENA);
+            let error = (decoded - v).abs();
+            assert!(error < COLLISION_CONFIG.death_radius);
+            assert!(error < 0.01);
+            v += 0.37;
+        }
+    }
+
+    #[test]
+    fn test_quantize_dir_round_trip() {
+        for i in 0..64 {
+            let angle = (i as f32) * TAU / 64.0;
+            let (dx, dz) = dequantize_dir(quantize_dir(angle.cos(), angle.sin()));
+            assert!((dx - angle.cos()).abs() < 0.001);
+            assert!((dz - angle.sin()).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_quantize_dir_non_unit_input() {
+        let (dx, dz) = dequantize_dir(quantize_dir(0.0, 5.0));
+        assert!(dx.abs() < 0.001);
+        assert!((dz - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_packed_player_state_round_trip() {
+        let player = PlayerState::new("p1".to_string(), 123.4, -56.7, 0.6, -0.8, true);
+        let packed = PackedPlayerState::pack(&player, ARENA);
+        let (x, z, dir_x, dir_z) = packed.unpack(ARENA);
+
+        assert!((x - player.x).abs() < COLLISION_CONFIG.death_radius);
+        assert!((z - player.z).abs() < COLLISION_CONFIG.death_radius);
+        assert!((dir_x - player.dir_x).abs() < 0.001);
+        assert!((dir_z - player.dir_z).abs() < 0.001);
+    }
+
+    fn straight_trail(start: f32, end: f32, step: f32) -> Vec<Vec2> {
+        let mut points = Vec::new();
+        let mut x = start;
+        while x <= end {
+            points.push(Vec2 { x, z: 0.0 });
+            x += step;
+        }
+        points
+    }
+
+    #[test]
+    fn test_decimate_keeps_near_points() {
+        let trail = straight_trail(0.0, 20.0, 1.0);
+        let viewer = Vec2 { x: 10.0, z: 0.0 };
+        let decimated = decimate_for_lod(&trail, &viewer, 50.0, 100.0);
+
+        assert_eq!(decimated.len(), trail.len());
+    }
+
+    #[test]
+    fn test_decimate_collapses_far_points() {
+        let trail = straight_trail(500.0, 600.0, 1.0);
+        let viewer = Vec2 { x: 0.0, z: 0.0 };
+        let decimated = decimate_for_lod(&trail, &viewer, 50.0, 100.0);
+
+        assert_eq!(decimated.len(), 2);
+        assert_eq!(decimated[0].x, 500.0);
+        assert_eq!(decimated[1].x, 600.0);
+    }
+
+    #[test]
+    fn test_decimate_mixed_distances() {
+        let trail = straight_trail(0.0, 200.0, 1.0);
+        let viewer = Vec2 { x: 0.0, z: 0.0 };
+        let decimated = decimate_for_lod(&trail, &viewer, 20.0, 100.0);
+
+        // Everything within `near` survives, the rest is thinned
+        let near_kept = decimated.iter().filter(|p| p.x <= 20.0).count();
+        assert_eq!(near_kept, 21);
+        assert!(decimated.len() < trail.len() / 2);
+        assert_eq!(decimated.last().unwrap().x, 200.0);
+    }
+
+    #[test]
+    fn test_decimate_short_trail_unchanged() {
+        let trail = straight_trail(500.0, 501.0, 1.0);
+        let viewer = Vec2 { x: 0.0, z: 0.0 };
+        assert_eq!(decimate_for_lod(&trail, &viewer, 10.0, 20.0).len(), 2);
+    }
+
+    fn hash_players() -> Vec<PlayerState> {
+        vec![
+            PlayerState::new("p1".to_string(), 10.0, -20.0, 1.0, 0.0, true),
+            PlayerState::new("p2".to_string(), -55.5, 30.25, 0.0, -1.0, false),
+        ]
+    }
+
+    #[test]
+    fn test_state_hash_identical_states_equal() {
+        let a = hash_players();
+        let mut b = hash_players();
+        assert_eq!(state_hash(&a), state_hash(&b));
+
+        // Order of the input slice doesn't matter
+        b.reverse();
+        assert_eq!(state_hash(&a), state_hash(&b));
+    }
+
+    #[test]
+    fn test_state_hash_position_change() {
+        let a = hash_players();
+        let mut b = hash_players();
+        b[0].x += 1.0;
+        assert_ne!(state_hash(&a), state_hash(&b));
+    }
+
+    #[test]
+    fn test_state_hash_stable_value() {
+        // FNV-1a of no input is the offset basis
+        assert_eq!(state_hash(&[]), 0xcbf2_9ce4_8422_2325);
+    }
+
+    #[test]
+    fn test_parse_trail_points_legacy_format() {
+        let points = parse_trail_points(r#"[{"x":1,"z":2},{"x":-3.5,"z":4}]"#).unwrap();
+        assert_eq!(points, vec![
+            TrailPoint { x: 1.0, z: 2.0, t: None },
+            TrailPoint { x: -3.5, z: 4.0, t: None },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_trail_points_timed_format() {
+        let points = parse_trail_points(r#"[ {"x": 1, "z": 2, "t": 30}, {"x": 5, "z": 2, "t": 45} ]"#).unwrap();
+        assert_eq!(points[0].t, Some(30));
+        assert_eq!(points[1], TrailPoint { x: 5.0, z: 2.0, t: Some(45) });
+    }
+
+    #[test]
+    fn test_parse_trail_segments_formats_match() {
+        let legacy = parse_trail_segments(r#"[{"x":0,"z":0},{"x":10,"z":0},{"x":10,"z":5}]"#).unwrap();
+        let timed = parse_trail_segments(
+            r#"[{"x":0,"z":0,"t":0},{"x":10,"z":0,"t":12},{"x":10,"z":5,"t":20}]"#,
+        ).unwrap();
+
+        let geometry = |s: &Segment| (s.start_x, s.start_z, s.end_x, s.end_z);
+        assert_eq!(legacy.len(), 2);
+        assert!(legacy.iter().map(geometry).eq(timed.iter().map(geometry)));
+        assert_eq!(geometry(&legacy[1]), (10.0, 0.0, 10.0, 5.0));
+    }
+
+    #[test]
+    fn test_parse_trail_segments_laid_at_from_ticks() {
+        let timed = parse_trail_segments(
+            r#"[{"x":0,"z":0,"t":0},{"x":10,"z":0,"t":120},{"x":10,"z":5}]"#,
+        ).unwrap();
+
+        assert_eq!(timed[0].laid_at, 120.0 / TRAIL_TICKS_PER_SECOND);
+        // No tick on the end point: fall back to the start point's
+        assert_eq!(timed[1].laid_at, 120.0 / TRAIL_TICKS_PER_SECOND);
+
+        let legacy = parse_trail_segments(r#"[{"x":0,"z":0},{"x":10,"z":0}]"#).unwrap();
+        assert_eq!(legacy[0].laid_at, 0.0);
+    }
+
+    #[test]
+    fn test_parse_trail_segments_empty_and_malformed() {
+        assert!(parse_trail_segments("[]").unwrap().is_empty());
+        assert!(parse_trail_segments(r#"[{"x":1,"z":2}]"#).unwrap().is_empty());
+        assert!(parse_trail_segments("not json").is_err());
+        assert!(parse_trail_segments(r#"[{"x":1}]"#).is_err());
+        assert!(parse_trail_segments(r#"[{"x":1,"z":2,"t":-4}]"#).is_err());
+    }
+
+    #[test]
+    fn test_format_point_fixed_decimals() {
+        assert_eq!(format_point(1.0), "1.00");
+        assert_eq!(format_point(-7.254), "-7.25");
+        assert_eq!(format_point(-0.001), "0.00");
+        assert_eq!(format_point(0.1 + 0.2), format_point(0.3));
+    }
+
+    #[test]
+    fn test_format_point_round_trip_stable() {
+        for v in [0.0, 12.345, -199.999, 87.5, -0.004] {
+            let once = format_point(v);
+            let twice = format_point(parse_point(&once).unwrap());
+            assert_eq!(once, twice);
+        }
+        assert_eq!(parse_point("NaN"), None);
+    }
+
+    #[test]
+    fn test_format_trail_points_round_trip() {
+        let points = vec![
+            TrailPoint { x: 1.0, z: -2.5, t: None },
+            TrailPoint { x: 10.126, z: 0.0, t: Some(12) },
+        ];
+        let json = format_trail_points(&points);
+        assert_eq!(json, r#"[{"x":1.00,"z":-2.50},{"x":10.13,"z":0.00,"t":12}]"#);
+
+        // Re-serializing the parsed trail is byte-identical
+        let parsed = parse_trail_points(&json).unwrap();
+        assert_eq!(format_trail_points(&parsed), json);
+    }
+
+    #[test]
+    fn test_corrupt_trail_does_not_block_other_collisions() {
+        let raw = [
+            ("p1", r#"[{"x":-5,"z":0},{"x":-1,"z":0}]"#),
+            ("p2", r#"[{"x":3,"z":"#),
+            ("p3", r#"[{"x":10,"z":-10},{"x":10,"z":10}]"#),
+        ];
+        let (trails, errors) = parse_all_trails(raw);
+
+        assert_eq!(trails.len(), 2);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "p2");
+
+        // p1 sits on p3's trail; p2 is still checked despite its own bad trail
+        let players = [
+            PlayerState::new("p1".to_string(), 10.0, 0.0, 1.0, 0.0, true),
+            PlayerState::new("p2".to_string(), 50.0, 50.0, 1.0, 0.0, true),
+        ];
+        let results = check_all_collisions(&players, &trails, &COLLISION_CONFIG);
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].1.collided);
+        assert!(!results[1].1.collided);
+    }
+
+    #[test]
+    fn test_display_speed_converges() {
+        let mut shown = 40.0;
+        let mut gap = 30.0;
+        for _ in 0..30 {
+            shown = display_speed(shown, 70.0, 0.2);
+            let new_gap = 70.0 - shown;
+            assert!(new_gap < gap && new_gap >= 0.0);
+            gap = new_gap;
+        }
+        assert!(gap < 0.1);
+    }
+
+    #[test]
+    fn test_display_speed_full_smoothing_snaps() {
+        assert_eq!(display_speed(40.0, 70.0, 1.0), 70.0);
+        assert_eq!(display_speed(40.0, 70.0, 0.0), 40.0);
+    }
+}
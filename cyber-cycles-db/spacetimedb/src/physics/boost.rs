@@ -0,0 +1,121 @@
+//! Boost energy system for Cyber Cycles
+//!
+//! Boosting drains a per-player energy bar that slowly refills. Emptying
+//! the bar starts a cooldown during which boosting is refused, even if a
+//! little energy has already come back, so players can't spam boost.
+
+use crate::physics::config::BoostConfig;
+
+/// State of a player's boost bar
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoostState {
+    /// Unique player identifier
+    pub player_id: String,
+    /// Remaining boost energy
+    pub energy: f32,
+    /// Seconds until boosting is allowed again after depletion
+    pub boost_cooldown_timer: f32,
+}
+
+impl BoostState {
+    /// Create a full boost bar for a player
+    pub fn new(player_id: impl Into<String>, config: &BoostConfig) -> Self {
+        Self {
+            player_id: player_id.into(),
+            energy: config.max_energy,
+            boost_cooldown_timer: 0.0,
+        }
+    }
+}
+
+/// Checks whether a player may boost right now
+///
+/// # Returns
+/// True if there is energy left and no cooldown is running
+pub fn can_boost(state: &BoostState) -> bool {
+    state.energy > 0.0 && state.boost_cooldown_timer <= 0.0
+}
+
+/// Advances a player's boost bar by one tick
+///
+/// Counts down the cooldown, then drains energy if the player is boosting
+/// and allowed to, or regenerates it otherwise. Emptying the bar starts
+/// the `boost_cooldown`.
+///
+/// # Arguments
+/// * `state` - Mutable reference to the boost state
+/// * `wants_boost` - Whether the player is holding boost
+/// * `dt` - Delta time in seconds
+/// * `config` - Boost configuration
+///
+/// # Returns
+/// True if the player is boosting this tick
+pub fn update_boost(state: &mut BoostState, wants_boost: bool, dt: f32, config: &BoostConfig) -> bool {
+    state.boost_cooldown_timer = (state.boost_cooldown_timer - dt).max(0.0);
+
+    let boosting = wants_boost && can_boost(state);
+    if boosting {
+        state.energy = (state.energy - config.drain_rate * dt).max(0.0);
+        if state.energy == 0.0 {
+            state.boost_cooldown_timer = config.boost_cooldown;
+        }
+    } else {
+        state.energy = (state.energy + config.regen_rate * dt).min(config.max_energy);
+    }
+
+    boosting
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drained(config: &BoostConfig) -> BoostState {
+        let mut state = BoostState::new("p1", config);
+        // 100 energy at 40/s empties in 2.5s
+        for _ in 0..3 {
+            update_boost(&mut state, true, 1.0, config);
+        }
+        state
+    }
+
+    #[test]
+    fn test_boost_allowed_with_energy() {
+        let config = BoostConfig::default();
+        let mut state = BoostState::new("p1", &config);
+
+        assert!(update_boost(&mut state, true, 0.5, &config));
+        assert_eq!(state.energy, 80.0);
+        assert_eq!(state.boost_cooldown_timer, 0.0);
+    }
+
+    #[test]
+    fn test_depletion_starts_cooldown() {
+        let config = BoostConfig::default();
+        let state = drained(&config);
+
+        assert_eq!(state.energy, 0.0);
+        assert_eq!(state.boost_cooldown_timer, config.boost_cooldown);
+    }
+
+    #[test]
+    fn test_boost_denied_during_cooldown() {
+        let config = BoostConfig::default();
+        let mut state = drained(&config);
+
+        // Some energy regenerates, but the cooldown still blocks boosting
+        assert!(!update_boost(&mut state, true, 0.5, &config));
+        assert!(state.energy > 0.0);
+        assert!(!can_boost(&state));
+    }
+
+    #[test]
+    fn test_boost_reenabled_after_cooldown() {
+        let config = BoostConfig::default();
+        let mut state = drained(&config);
+
+        update_boost(&mut state, false, config.boost_cooldown, &config);
+        assert_eq!(state.boost_cooldown_timer, 0.0);
+        assert!(update_boost(&mut state, true, 0.1, &config));
+    }
+}
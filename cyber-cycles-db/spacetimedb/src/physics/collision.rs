@@ -5,7 +5,11 @@
 //! - Arena boundaries
 //! - Continuous collision checking for fast-moving objects
 
-use crate::physics::config::CollisionConfig;
+use std::collections::HashMap;
+
+use crate::physics::config::{BoundsMode, CollisionConfig, TeamConfig};
+use crate::physics::rubber::death_radius_with_rubber;
+use crate::Vec2;
 
 /// Epsilon constant for floating-point comparisons
 pub const EPS: f32 = 0.01;
@@ -18,8 +22,53 @@ pub const COLLISION_CONFIG: CollisionConfig = CollisionConfig {
     wall_collision_dist: 1.0,
     slipstream_distance: 5.0,
     slipstream_angle: 0.3,
+    eps: EPS,
+    trail_expiry: false,
+    trail_lifetime: 60.0,
+    bounds_mode: BoundsMode::Wall,
+    nearest_trails: 8,
 };
 
+/// Capability bit: swept checks for fast bikes (`continuous_collision_check`)
+pub const CAP_CONTINUOUS: u32 = 1 << 0;
+/// Capability bit: bike-vs-bike collisions (reserved, not yet detected)
+pub const CAP_BIKE_BIKE: u32 = 1 << 1;
+/// Capability bit: wrap-around arena bounds (`BoundsMode::Wrap`)
+pub const CAP_WRAP: u32 = 1 << 2;
+
+/// Collision features this server supports, as `CAP_*` bitflags
+///
+/// Stored in `GlobalConfig` so clients can enable matching prediction.
+/// New features add a bit here; bits are never reused.
+pub const fn collision_capabilities() -> u32 {
+    CAP_CONTINUOUS | CAP_WRAP
+}
+
+/// Adds a capability bit to a set of flags
+pub const fn with_capability(flags: u32, cap: u32) -> u32 {
+    flags | cap
+}
+
+/// Checks whether every bit of `cap` is set in `flags`
+pub const fn has_capability(flags: u32, cap: u32) -> bool {
+    flags & cap == cap
+}
+
+/// Calculates the collision width of a trail laid at a given speed
+///
+/// Faster bikes lay wider, deadlier walls.
+///
+/// # Arguments
+/// * `speed` - Speed the segment was laid at (units/sec)
+/// * `base` - Width at zero speed (units)
+/// * `speed_factor` - Extra width per unit of speed
+///
+/// # Returns
+/// Trail width (units, never negative)
+pub fn trail_width(speed: f32, base: f32, speed_factor: f32) -> f32 {
+    (base + speed.max(0.0) * speed_factor).max(0.0)
+}
+
 /// A line segment in 2D space (XZ plane)
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Segment {
@@ -27,12 +76,16 @@ pub struct Segment {
     pub start_z: f32,
     pub end_x: f32,
     pub end_z: f32,
+    /// Extra collision band around the segment (see `trail_width`)
+    pub width: f32,
+    /// Round time the segment was laid at (seconds), for trail expiry
+    pub laid_at: f32,
 }
 
 impl Segment {
     /// Create a new segment from two points
     pub fn new(start_x: f32, start_z: f32, end_x: f32, end_z: f32) -> Self {
-        Self { start_x, start_z, end_x, end_z }
+        Self { start_x, start_z, end_x, end_z, width: 0.0, laid_at: 0.0 }
     }
 
     /// Create a segment from a player's current and previous position
@@ -42,9 +95,23 @@ impl Segment {
             start_z: prev_z,
             end_x: curr_x,
             end_z: curr_z,
+            width: 0.0,
+            laid_at: 0.0,
         }
     }
 
+    /// Set the segment's collision width, e.g. from the speed it was laid at
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the round time the segment was laid at
+    pub fn with_laid_at(mut self, laid_at: f32) -> Self {
+        self.laid_at = laid_at;
+        self
+    }
+
     /// Get the length of the segment
     pub fn length(&self) -> f32 {
         let dx = self.end_x - self.start_x;
@@ -62,6 +129,12 @@ pub struct PlayerState {
     pub dir_x: f32,
     pub dir_z: f32,
     pub alive: bool,
+    /// Extra hitbox radius added to the death radius (0 for a normal bike)
+    pub radius: f32,
+    /// Whether the bike has cleared its spawn point, so its own trail is live
+    pub trail_armed: bool,
+    /// Owners whose trails this bike passes through (scripted scenarios)
+    pub immune_to: Vec<String>,
 }
 
 impl PlayerState {
@@ -74,7 +147,26 @@ impl PlayerState {
         dir_z: f32,
         alive: bool,
     ) -> Self {
-        Self { id, x, z, dir_x, dir_z, alive }
+        Self { id, x, z, dir_x, dir_z, alive, radius: 0.0, trail_armed: true, immune_to: Vec::new() }
+    }
+
+    /// Set the bike's extra hitbox radius, e.g. from a grow/shrink power-up
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Set the owners whose trails this bike ignores
+    pub fn with_immunity(mut self, immune_to: Vec<String>) -> Self {
+        self.immune_to = immune_to;
+        self
+    }
+
+    /// Whether hits on `owner_id`'s trail are ignored for this bike
+    ///
+    /// A bike is never immune to its own trail.
+    pub fn is_immune_to(&self, owner_id: &str) -> bool {
+        owner_id != self.id && self.immune_to.iter().any(|id| id == owner_id)
     }
 }
 
@@ -85,6 +177,10 @@ pub struct CollisionResult {
     pub collision_type: Option<CollisionType>,
     pub distance: f32,
     pub segment_index: Option<usize>,
+    /// Unit surface normal at the impact, pointing toward the bike
+    pub normal: Option<Vec2>,
+    /// Bike speed along the impact normal (0 until `with_impact_speed`)
+    pub impact_speed: f32,
 }
 
 impl Default for CollisionResult {
@@ -94,19 +190,149 @@ impl Default for CollisionResult {
             collision_type: None,
             distance: f32::MAX,
             segment_index: None,
+            normal: None,
+            impact_speed: 0.0,
+        }
+    }
+}
+
+impl CollisionResult {
+    /// Fill in `impact_speed` for a bike moving at `speed`
+    ///
+    /// Collision checks don't know the bike's speed, so callers that need
+    /// damage tiers add it afterwards. Results without a normal keep 0.
+    pub fn with_impact_speed(mut self, player: &PlayerState, speed: f32) -> Self {
+        if let Some(normal) = &self.normal {
+            self.impact_speed = impact_speed(&Vec2 { x: player.dir_x, z: player.dir_z }, speed, normal);
+        }
+        self
+    }
+}
+
+/// Impact speed at or above which a hit is `Hard`
+pub const IMPACT_HARD_SPEED: f32 = 15.0;
+
+/// Impact speed at or above which a hit is `Fatal`
+pub const IMPACT_FATAL_SPEED: f32 = 50.0;
+
+/// How hard a bike hit something, for health-based modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImpactSeverity {
+    /// Scrape along a surface
+    Glance,
+    /// Solid hit that should hurt
+    Hard,
+    /// Hit hard enough to destroy the bike outright
+    Fatal,
+}
+
+impl ImpactSeverity {
+    /// Classify an impact by `impact_speed`
+    pub fn from_impact_speed(impact_speed: f32) -> Self {
+        if impact_speed >= IMPACT_FATAL_SPEED {
+            ImpactSeverity::Fatal
+        } else if impact_speed >= IMPACT_HARD_SPEED {
+            ImpactSeverity::Hard
+        } else {
+            ImpactSeverity::Glance
         }
     }
 }
 
+/// Speed of a bike along a surface normal
+///
+/// A head-on hit carries the full speed; sliding along the surface
+/// carries none.
+///
+/// # Arguments
+/// * `dir` - Bike heading (need not be normalized)
+/// * `speed` - Bike speed (units/sec)
+/// * `normal` - Unit surface normal at the impact
+///
+/// # Returns
+/// Non-negative impact speed (0 for a zero heading)
+pub fn impact_speed(dir: &Vec2, speed: f32, normal: &Vec2) -> f32 {
+    let len = (dir.x * dir.x + dir.z * dir.z).sqrt();
+    if len < EPS {
+        return 0.0;
+    }
+    (speed * (dir.x * normal.x + dir.z * normal.z) / len).abs()
+}
+
 /// Type of collision detected
 #[derive(Debug, Clone, PartialEq)]
 pub enum CollisionType {
     /// Collision with own trail
     SelfTrail,
-    /// Collision with another player's trail
+    /// Collision with another player's trail (owner id is never empty)
     OtherTrail(String),
+    /// Collision with a trail whose owner isn't known
+    UnknownTrail,
+    /// Collision with a neutral obstacle, such as a dead bike's leftover trail
+    Hazard,
     /// Collision with arena wall
     Wall,
+    /// Collision with another bike (holds that bike's id)
+    Bike(String),
+}
+
+/// Short human-readable cause of death, for kill messages
+impl std::fmt::Display for CollisionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CollisionType::SelfTrail => write!(f, "own trail"),
+            CollisionType::OtherTrail(owner) => write!(f, "trail of {}", owner),
+            CollisionType::UnknownTrail => write!(f, "unknown trail"),
+            CollisionType::Hazard => write!(f, "hazard"),
+            CollisionType::Wall => write!(f, "wall"),
+            CollisionType::Bike(other) => write!(f, "bike {}", other),
+        }
+    }
+}
+
+/// Running counts of collisions by type, for balancing telemetry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollisionStats {
+    /// Deaths on the player's own trail
+    pub self_trail: u32,
+    /// Deaths on another player's trail, a trail of unknown owner or a hazard
+    pub other_trail: u32,
+    /// Deaths on the arena wall
+    pub wall: u32,
+    /// Deaths from hitting another bike
+    pub bike: u32,
+}
+
+impl CollisionStats {
+    /// Count one collision of the given type
+    pub fn record_collision(&mut self, kind: &CollisionType) {
+        match kind {
+            CollisionType::SelfTrail => self.self_trail += 1,
+            // Unattributed trails and hazards can't be the player's own
+            CollisionType::OtherTrail(_) | CollisionType::UnknownTrail | CollisionType::Hazard => {
+                self.other_trail += 1
+            }
+            CollisionType::Wall => self.wall += 1,
+            CollisionType::Bike(_) => self.bike += 1,
+        }
+    }
+
+    /// Total collisions recorded across all types
+    pub fn total(&self) -> u32 {
+        self.self_trail + self.other_trail + self.wall + self.bike
+    }
+}
+
+/// Reusable output buffers for per-tick collision queries
+///
+/// The tick owns one of these and passes it to the `_into` query variants,
+/// which clear and refill the buffers instead of allocating fresh vectors.
+#[derive(Debug, Clone, Default)]
+pub struct CollisionScratch {
+    /// Output of `find_segments_within_distance_into`
+    pub nearby: Vec<(usize, f32)>,
+    /// Output of `check_all_collisions_into`
+    pub collisions: Vec<(String, CollisionResult)>,
 }
 
 /// Calculates the squared distance from a point to a line segment
@@ -125,13 +351,35 @@ pub fn distance_to_segment_squared(
     px: f32, pz: f32,
     sx: f32, sz: f32,
     ex: f32, ez: f32,
+) -> f32 {
+    distance_to_segment_squared_with_eps(px, pz, sx, sz, ex, ez, EPS)
+}
+
+/// Calculates the squared distance from a point to a line segment
+/// using a custom tolerance
+///
+/// Segments shorter than `eps` are treated as a single point.
+///
+/// # Arguments
+/// * `px`, `pz` - Point to check
+/// * `sx`, `sz` - Segment start point
+/// * `ex`, `ez` - Segment end point
+/// * `eps` - Floating-point tolerance (see `CollisionConfig::eps`)
+///
+/// # Returns
+/// Squared distance from point to segment
+pub fn distance_to_segment_squared_with_eps(
+    px: f32, pz: f32,
+    sx: f32, sz: f32,
+    ex: f32, ez: f32,
+    eps: f32,
 ) -> f32 {
     let dx = ex - sx;
     let dz = ez - sz;
     
     // Handle degenerate segment (single point)
     let segment_len_sq = dx * dx + dz * dz;
-    if segment_len_sq < EPS * EPS {
+    if segment_len_sq < eps * eps {
         let pdx = px - sx;
         let pdz = pz - sz;
         return pdx * pdx + pdz * pdz;
@@ -187,31 +435,70 @@ pub fn distance_to_segment_struct(px: f32, pz: f32, segment: &Segment) -> f32 {
 /// * `segments` - Slice of trail segments to check against
 /// * `death_radius` - Distance threshold for collision
 ///
+/// The player's own `radius` and each segment's `width` are added to
+/// `death_radius`, so larger bikes and faster trails have bigger hitboxes.
+///
 /// # Returns
 /// CollisionResult with collision details
 pub fn check_trail_collision(
     player: &PlayerState,
     segments: &[Segment],
     death_radius: f32,
+) -> CollisionResult {
+    check_trail_collision_filtered(player, segments, death_radius, false)
+}
+
+/// Checks for trail collision, optionally ignoring segments behind the bike
+///
+/// With `ignore_behind` set, segments whose midpoint lies more than 90°
+/// from the bike's heading are skipped. Bikes can't reverse, so these can
+/// only produce rear-contact kills. Long segments that run alongside the
+/// bike may have a midpoint behind it, so leave the filter off where that
+/// matters.
+///
+/// # Arguments
+/// * `player` - Player state to check
+/// * `segments` - Slice of trail segments to check against
+/// * `death_radius` - Distance threshold for collision
+/// * `ignore_behind` - Skip segments behind the bike
+///
+/// # Returns
+/// CollisionResult with collision details; skipped segments never set
+/// `segment_index`
+pub fn check_trail_collision_filtered(
+    player: &PlayerState,
+    segments: &[Segment],
+    death_radius: f32,
+    ignore_behind: bool,
 ) -> CollisionResult {
     if !player.alive {
         return CollisionResult::default();
     }
     
-    let death_radius_sq = death_radius * death_radius;
+    let hit_radius = death_radius + player.radius;
     let mut result = CollisionResult::default();
     
     for (index, segment) in segments.iter().enumerate() {
+        if ignore_behind {
+            let mid_x = (segment.start_x + segment.end_x) * 0.5 - player.x;
+            let mid_z = (segment.start_z + segment.end_z) * 0.5 - player.z;
+            if mid_x * player.dir_x + mid_z * player.dir_z < 0.0 {
+                continue;
+            }
+        }
+        
         let dist_sq = distance_to_segment_squared(
             player.x, player.z,
             segment.start_x, segment.start_z,
             segment.end_x, segment.end_z,
         );
+        let band = hit_radius + segment.width;
         
-        if dist_sq < death_radius_sq {
+        if dist_sq < band * band {
             result.collided = true;
             result.distance = dist_sq.sqrt();
             result.segment_index = Some(index);
+            result.normal = Some(segment_normal_at(&Vec2 { x: player.x, z: player.z }, segment));
             return result;
         }
         
@@ -226,6 +513,29 @@ pub fn check_trail_collision(
     result
 }
 
+/// Owner id for trail segments that are neutral hazards rather than anyone's trail
+///
+/// Never a valid player id, so hazards can't be mistaken for a real owner.
+pub const HAZARD_OWNER: &str = "#hazard";
+
+/// Classifies a hit on `owner_id`'s trail
+///
+/// An empty owner id can't be attributed to anyone, so it becomes
+/// `UnknownTrail` rather than `OtherTrail("")` (or a bogus `SelfTrail` for a
+/// bike that also has an empty id). Segments owned by `HAZARD_OWNER` are a
+/// `Hazard`.
+pub fn trail_collision_type(player_id: &str, owner_id: &str) -> CollisionType {
+    if owner_id.is_empty() {
+        CollisionType::UnknownTrail
+    } else if owner_id == HAZARD_OWNER {
+        CollisionType::Hazard
+    } else if owner_id == player_id {
+        CollisionType::SelfTrail
+    } else {
+        CollisionType::OtherTrail(owner_id.to_string())
+    }
+}
+
 /// Checks for collision with a specific player's trail
 ///
 /// # Arguments
@@ -245,11 +555,7 @@ pub fn check_trail_collision_with_owner(
     let mut result = check_trail_collision(player, segments, death_radius);
     
     if result.collided {
-        if player.id == trail_owner_id {
-            result.collision_type = Some(CollisionType::SelfTrail);
-        } else {
-            result.collision_type = Some(CollisionType::OtherTrail(trail_owner_id.to_string()));
-        }
+        result.collision_type = Some(trail_collision_type(&player.id, trail_owner_id));
     }
     
     result
@@ -267,6 +573,13 @@ pub fn check_trail_collision_with_owner(
 ///
 /// # Returns
 /// CollisionResult indicating if collision occurred along the path
+///
+/// A stationary bike (movement shorter than `EPS`) has no meaningful path,
+/// so it falls back to a point check at the current position using the
+/// default death radius.
+///
+/// `segment_index` is only `Some` when `collided` is true, so callers can
+/// unwrap it after checking for a hit. An empty slice never collides.
 pub fn continuous_collision_check(
     prev_x: f32, prev_z: f32,
     curr_x: f32, curr_z: f32,
@@ -277,550 +590,2569 @@ pub fn continuous_collision_check(
     // Create a segment from the player's movement
     let movement_segment = Segment::from_positions(prev_x, prev_z, curr_x, curr_z);
     
+    // Degenerate movement: check the current position as a point
+    if movement_segment.length() < EPS {
+        let stationary = PlayerState::new(String::new(), curr_x, curr_z, 0.0, 0.0, true);
+        let point = check_trail_collision(&stationary, segments, COLLISION_CONFIG.death_radius);
+        if point.collided {
+            result = point;
+            result.collision_type = Some(CollisionType::UnknownTrail);
+        }
+        debug_assert!(result.segment_index.is_none() || result.collided);
+        return result;
+    }
+    
     for (index, segment) in segments.iter().enumerate() {
         if segments_intersect(&movement_segment, segment) {
             result.collided = true;
             result.segment_index = Some(index);
-            result.collision_type = Some(CollisionType::OtherTrail(String::new()));
+            // The bike approached from where it was last tick
+            result.normal = Some(segment_normal_at(&Vec2 { x: prev_x, z: prev_z }, segment));
+            result.collision_type = Some(CollisionType::UnknownTrail);
             return result;
         }
     }
     
+    debug_assert!(result.segment_index.is_none());
     result
 }
 
-/// Checks if two line segments intersect
+/// Checks every alive player against every trail in one pass
 ///
-/// Uses the cross product method to determine intersection.
+/// This is the single entry point for the tick. Own-trail handling follows
+/// `closest_collision_among`, and only the `config.nearest_trails` nearest
+/// owners get a full check (see `closest_collision_among_nearest`). With
+/// trail expiry on, call `prune_expired_trails` first.
 ///
 /// # Arguments
-/// * `s1` - First segment
-/// * `s2` - Second segment
+/// * `players` - All player states
+/// * `trails` - Trail segments keyed by owner id
+/// * `config` - Collision configuration (uses `death_radius` and `nearest_trails`)
 ///
 /// # Returns
-/// True if segments intersect
-pub fn segments_intersect(s1: &Segment, s2: &Segment) -> bool {
-    let d1 = direction(s2, &s1.start());
-    let d2 = direction(s2, &s1.end());
-    let d3 = direction(s1, &s2.start());
-    let d4 = direction(s1, &s2.end());
-    
-    // General case: segments straddle each other
-    if ((d1 > EPS && d2 < -EPS) || (d1 < -EPS && d2 > EPS))
-        && ((d3 > EPS && d4 < -EPS) || (d3 < -EPS && d4 > EPS))
-    {
-        return true;
-    }
-    
-    // Special cases: endpoints lie on the other segment
-    if d1.abs() < EPS && on_segment(s2, &s1.start()) { return true; }
-    if d2.abs() < EPS && on_segment(s2, &s1.end()) { return true; }
-    if d3.abs() < EPS && on_segment(s1, &s2.start()) { return true; }
-    if d4.abs() < EPS && on_segment(s1, &s2.end()) { return true; }
-    
-    false
+/// One (player_id, CollisionResult) per alive player, holding the nearest
+/// collision or, if none, the nearest miss
+pub fn check_all_collisions(
+    players: &[PlayerState],
+    trails: &[(String, Vec<Segment>)],
+    config: &CollisionConfig,
+) -> Vec<(String, CollisionResult)> {
+    let mut scratch = CollisionScratch::default();
+    check_all_collisions_into(&mut scratch, players, trails, config);
+    scratch.collisions
 }
 
-/// Calculates the direction/cross product of three points
+/// Checks every alive player, shrinking hitboxes for players on the rubber band
+///
+/// Like `check_all_collisions`, but each player's death radius comes from
+/// `death_radius_with_rubber` using their rubber effectiveness. Players
+/// missing from `effectiveness` use the normal radius; build the map with
+/// `active_effectiveness` so only players the rubber band is helping qualify.
 ///
 /// # Arguments
-/// * `s` - Segment to use as reference
-/// * `p` - Point to check (as (x, z) tuple)
+/// * `players` - All player states
+/// * `trails` - Trail segments keyed by owner id
+/// * `config` - Collision configuration (uses `death_radius`)
+/// * `effectiveness` - Rubber effectiveness keyed by player id
+/// * `max_reduction` - Radius removed at full effectiveness (units)
 ///
 /// # Returns
-/// Cross product value (positive = left, negative = right, zero = collinear)
-fn direction(s: &Segment, p: &(f32, f32)) -> f32 {
-    let (px, pz) = p;
-    let dx1 = px - s.start_x;
-    let dz1 = pz - s.start_z;
-    let dx2 = s.end_x - s.start_x;
-    let dz2 = s.end_z - s.start_z;
-    
-    dx1 * dz2 - dz1 * dx2
+/// One (player_id, CollisionResult) per alive player
+pub fn check_all_collisions_with_rubber(
+    players: &[PlayerState],
+    trails: &[(String, Vec<Segment>)],
+    config: &CollisionConfig,
+    effectiveness: &HashMap<String, f32>,
+    max_reduction: f32,
+) -> Vec<(String, CollisionResult)> {
+    players
+        .iter()
+        .filter(|p| p.alive)
+        .map(|player| {
+            let eff = effectiveness.get(&player.id).copied().unwrap_or(0.0);
+            let radius = death_radius_with_rubber(config.death_radius, eff, max_reduction);
+            (player.id.clone(), closest_collision_among(player, trails, radius))
+        })
+        .collect()
 }
 
-/// Checks if a point lies on a segment (assumes collinearity)
+/// Checks every alive player, sparing teammates' trails unless friendly fire is on
+///
+/// Like `check_all_collisions`, but a hit on a trail whose owner shares the
+/// player's team is ignored when `team_config.friendly_fire` is off. The
+/// player's own trail always counts, and players without a team are
+/// everyone's enemy.
 ///
 /// # Arguments
-/// * `s` - Segment
-/// * `p` - Point to check (as (x, z) tuple)
+/// * `players` - All player states
+/// * `trails` - Trail segments keyed by owner id
+/// * `config` - Collision configuration (uses `death_radius`)
+/// * `teams` - Team id keyed by player id
+/// * `team_config` - Team mode configuration
 ///
 /// # Returns
-/// True if point is on the segment
-fn on_segment(s: &Segment, p: &(f32, f32)) -> bool {
-    let px = p.0;
-    let pz = p.1;
-    let min_x = s.start_x.min(s.end_x) - EPS;
-    let max_x = s.start_x.max(s.end_x) + EPS;
-    let min_z = s.start_z.min(s.end_z) - EPS;
-    let max_z = s.start_z.max(s.end_z) + EPS;
-    
-    (px >= min_x && px <= max_x) && (pz >= min_z && pz <= max_z)
-}
-
-impl Segment {
-    /// Get the start point as a tuple
-    pub fn start(&self) -> (f32, f32) {
-        (self.start_x, self.start_z)
-    }
-    
-    /// Get the end point as a tuple
-    pub fn end(&self) -> (f32, f32) {
-        (self.end_x, self.end_z)
-    }
+/// One (player_id, CollisionResult) per alive player
+pub fn check_all_collisions_with_teams(
+    players: &[PlayerState],
+    trails: &[(String, Vec<Segment>)],
+    config: &CollisionConfig,
+    teams: &HashMap<String, u32>,
+    team_config: &TeamConfig,
+) -> Vec<(String, CollisionResult)> {
+    players
+        .iter()
+        .filter(|p| p.alive)
+        .map(|player| {
+            let team = teams.get(&player.id);
+            let counts = |owner_id: &str| {
+                team_config.friendly_fire
+                    || owner_id == player.id
+                    || team.is_none()
+                    || teams.get(owner_id) != team
+            };
+            let result = closest_collision_among_where(player, trails, config.death_radius, counts);
+            (player.id.clone(), result)
+        })
+        .collect()
 }
 
-/// Checks if a position is within arena bounds
+/// Allocation-free form of `check_all_collisions`
 ///
-/// # Arguments
-/// * `x`, `z` - Position to check
-/// * `arena_size` - Half-size of the arena (arena extends from -size to +size)
+/// Results replace the contents of `scratch.collisions`.
 ///
 /// # Returns
-/// * `Ok(())` if within bounds
-/// * `Err` with position details if out of bounds
-pub fn check_arena_bounds(
-    x: f32, z: f32, arena_size: f32,
-) -> Result<(), crate::physics::PhysicsError> {
-    let bound = arena_size - COLLISION_CONFIG.wall_collision_dist;
-    
-    if x.abs() > bound || z.abs() > bound {
-        Err(crate::physics::PhysicsError::OutOfBounds { x, z, arena_size })
-    } else {
-        Ok(())
-    }
+/// The refilled `scratch.collisions`
+pub fn check_all_collisions_into<'a>(
+    scratch: &'a mut CollisionScratch,
+    players: &[PlayerState],
+    trails: &[(String, Vec<Segment>)],
+    config: &CollisionConfig,
+) -> &'a [(String, CollisionResult)] {
+    scratch.collisions.clear();
+    scratch.collisions.extend(players.iter().filter(|p| p.alive).map(|player| {
+        let result = closest_collision_among_nearest(player, trails, config.death_radius, config.nearest_trails);
+        (player.id.clone(), result)
+    }));
+    &scratch.collisions
 }
 
-/// Checks for collision with arena walls
+/// Removes trail segments older than `config.trail_lifetime`
+///
+/// Keeps long rounds playable by letting old walls fade. Does nothing
+/// unless `config.trail_expiry` is set.
 ///
 /// # Arguments
-/// * `x`, `z` - Position to check
-/// * `arena_size` - Half-size of the arena
-/// * `wall_distance` - Distance from edge to consider as collision
+/// * `trails` - Trail segments keyed by owner id
+/// * `now` - Current round time (seconds)
+/// * `config` - Collision configuration
 ///
 /// # Returns
-/// True if colliding with wall
-pub fn check_wall_collision(
-    x: f32, z: f32, arena_size: f32, wall_distance: f32,
-) -> bool {
-    let bound = arena_size - wall_distance;
-    x.abs() >= bound || z.abs() >= bound
+/// Number of segments removed
+pub fn prune_expired_trails(
+    trails: &mut [(String, Vec<Segment>)],
+    now: f32,
+    config: &CollisionConfig,
+) -> usize {
+    if !config.trail_expiry {
+        return 0;
+    }
+
+    let mut removed = 0;
+    for (_, segments) in trails.iter_mut() {
+        let before = segments.len();
+        segments.retain(|s| now - s.laid_at <= config.trail_lifetime);
+        removed += before - segments.len();
+    }
+    removed
 }
 
-/// Checks for slipstream effect from another player
+/// Finds the nearest collision across several players' trails
+///
+/// Each owner's trail is checked with `check_trail_collision_with_owner`,
+/// and the closest hit wins so the kill feed credits the right owner.
+/// For a player's own trail the last segment is skipped, since the bike's
+/// head always sits on the segment it is currently drawing. A bike whose
+/// trail isn't armed yet ignores its own trail entirely (see
+/// `has_cleared_spawn`), and trails of owners in `immune_to` are skipped.
 ///
 /// # Arguments
-/// * `player` - Player to check slipstream for
-/// * `leader` - Potential slipstream leader
-/// * `slipstream_distance` - Maximum distance for slipstream
-/// * `slipstream_angle` - Maximum angle for slipstream (radians)
+/// * `player` - Player state to check
+/// * `trails` - Trail segments keyed by owner id
+/// * `death_radius` - Collision threshold
 ///
 /// # Returns
-/// True if player is in slipstream of leader
-pub fn check_slipstream(
+/// The nearest collision, or the nearest miss if nothing was hit
+pub fn closest_collision_among(
     player: &PlayerState,
-    leader: &PlayerState,
-    slipstream_distance: f32,
-    slipstream_angle: f32,
-) -> bool {
-    // Vector from player to leader
-    let dx = leader.x - player.x;
-    let dz = leader.z - player.z;
-    let dist_sq = dx * dx + dz * dz;
-    
-    // Check distance
-    if dist_sq > slipstream_distance * slipstream_distance {
-        return false;
-    }
-    
-    // Check angle (player should be facing toward leader)
-    let dist = dist_sq.sqrt();
-    if dist < EPS {
-        return false;
-    }
-    
-    // Normalize direction to leader
-    let to_leader_x = dx / dist;
-    let to_leader_z = dz / dist;
-    
-    // Dot product with player direction
-    let dot = player.dir_x * to_leader_x + player.dir_z * to_leader_z;
-    
-    // Check if angle is within slipstream cone
-    dot > slipstream_angle.cos()
+    trails: &[(String, Vec<Segment>)],
+    death_radius: f32,
+) -> CollisionResult {
+    closest_collision_among_where(player, trails, death_radius, |_| true)
 }
 
-/// Finds the closest segment to a point
-///
-/// # Arguments
-/// * `px`, `pz` - Point to check
-/// * `segments` - Segments to search
+/// Distance from a point to the bounding box of a trail, less its width
 ///
 /// # Returns
-/// Tuple of (segment_index, distance) or None if no segments
-pub fn find_closest_segment(
-    px: f32, pz: f32,
-    segments: &[Segment],
-) -> Option<(usize, f32)> {
+/// 0.0 inside the box, `f32::INFINITY` for an empty trail
+fn trail_bounds_distance(x: f32, z: f32, segments: &[Segment]) -> f32 {
     if segments.is_empty() {
-        return None;
+        return f32::INFINITY;
     }
-    
-    let mut closest_idx = 0;
-    let mut closest_dist = f32::MAX;
-    
-    for (idx, segment) in segments.iter().enumerate() {
-        let dist = distance_to_segment_struct(px, pz, segment);
-        if dist < closest_dist {
-            closest_dist = dist;
-            closest_idx = idx;
-        }
+
+    let (mut min_x, mut min_z) = (f32::INFINITY, f32::INFINITY);
+    let (mut max_x, mut max_z) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+    let mut width = 0.0f32;
+    for s in segments {
+        min_x = min_x.min(s.start_x).min(s.end_x);
+        min_z = min_z.min(s.start_z).min(s.end_z);
+        max_x = max_x.max(s.start_x).max(s.end_x);
+        max_z = max_z.max(s.start_z).max(s.end_z);
+        width = width.max(s.width);
     }
-    
-    Some((closest_idx, closest_dist))
+
+    // Wide segments reach past their endpoints' box
+    let dx = (min_x - x).max(x - max_x).max(0.0);
+    let dz = (min_z - z).max(z - max_z).max(0.0);
+    ((dx * dx + dz * dz).sqrt() - width).max(0.0)
 }
 
-/// Gets all segments within a certain distance of a point
+/// Picks the owners whose trails are worth a full collision check
 ///
-/// # Arguments
-/// * `px`, `pz` - Point to check
-/// * `segments` - Segments to search
-/// * `max_distance` - Maximum distance threshold
+/// Trails are ranked by the distance from the player to their bounding
+/// box, a cheap lower bound on the distance to any segment. Ties keep the
+/// order of `trails`, and owners with no segments are left out.
 ///
-/// # Returns
-/// Vector of (segment_index, distance) tuples
-pub fn find_segments_within_distance(
-    px: f32, pz: f32,
+/// The ranking alone can't rule a trail out: several large boxes may all
+/// contain the bike. Every owner whose bound is within `death_radius` is
+/// therefore kept, even past `k`.
+///
+/// # Arguments
+/// * `player` - Player state to check
+/// * `trails` - Trail segments keyed by owner id
+/// * `k` - Owners to return beyond those within `death_radius`
+/// * `death_radius` - Collision threshold
+///
+/// # Returns
+/// Owner ids, nearest first: every one that could collide, padded out to
+/// `k` with the nearest misses
+pub fn nearest_trail_owners<'a>(
+    player: &PlayerState,
+    trails: &'a [(String, Vec<Segment>)],
+    k: usize,
+    death_radius: f32,
+) -> Vec<&'a str> {
+    let mut ranked: Vec<(f32, &str)> = trails
+        .iter()
+        .map(|(owner_id, segments)| (trail_bounds_distance(player.x, player.z, segments), owner_id.as_str()))
+        .filter(|(distance, _)| distance.is_finite())
+        .collect();
+    ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+    ranked
+        .into_iter()
+        .enumerate()
+        .take_while(|(rank, (distance, _))| *rank < k || *distance <= death_radius)
+        .map(|(_, (_, owner_id))| owner_id)
+        .collect()
+}
+
+/// `closest_collision_among`, checking only the `k` nearest trails
+///
+/// With many players most trails are nowhere near a given bike; this skips
+/// them using `nearest_trail_owners`. Trails close enough to collide are
+/// always checked, so only the reported nearest miss depends on `k`. A `k`
+/// of 0 checks every trail.
+///
+/// # Arguments
+/// * `player` - Player state to check
+/// * `trails` - Trail segments keyed by owner id
+/// * `death_radius` - Collision threshold
+/// * `k` - Owners to check in full (0 = all)
+///
+/// # Returns
+/// The nearest collision among the checked trails, or the nearest miss
+pub fn closest_collision_among_nearest(
+    player: &PlayerState,
+    trails: &[(String, Vec<Segment>)],
+    death_radius: f32,
+    k: usize,
+) -> CollisionResult {
+    if k == 0 || k >= trails.len() {
+        return closest_collision_among(player, trails, death_radius);
+    }
+
+    let owners = nearest_trail_owners(player, trails, k, death_radius);
+    closest_collision_among_where(player, trails, death_radius, |owner_id| owners.contains(&owner_id))
+}
+
+/// `closest_collision_among`, limited to owners accepted by `include`
+fn closest_collision_among_where(
+    player: &PlayerState,
+    trails: &[(String, Vec<Segment>)],
+    death_radius: f32,
+    include: impl Fn(&str) -> bool,
+) -> CollisionResult {
+    let mut nearest = CollisionResult::default();
+
+    let counts = |owner_id: &str| include(owner_id) && !player.is_immune_to(owner_id);
+    for (owner_id, segments) in trails.iter().filter(|(owner_id, _)| counts(owner_id)) {
+        let segments = if *owner_id == player.id {
+            if !player.trail_armed {
+                continue;
+            }
+            &segments[..segments.len().saturating_sub(1)]
+        } else {
+            &segments[..]
+        };
+
+        let result = nearest_trail_collision_with_owner(player, owner_id, segments, death_radius);
+        if beats(&result, &nearest) {
+            nearest = result;
+        }
+    }
+
+    nearest
+}
+
+/// Whether `result` is a closer collision (or miss) than `nearest`
+///
+/// Any hit beats a miss; otherwise the smaller distance wins.
+fn beats(result: &CollisionResult, nearest: &CollisionResult) -> bool {
+    match (result.collided, nearest.collided) {
+        (true, false) => true,
+        (false, true) => false,
+        _ => result.distance < nearest.distance,
+    }
+}
+
+/// `check_trail_collision_with_owner`, reporting the nearest segment hit
+///
+/// `check_trail_collision` stops at the first segment in range, which is
+/// enough to know a bike crashed but not how close the hit was. Comparing
+/// owners needs each owner's true minimum distance.
+fn nearest_trail_collision_with_owner(
+    player: &PlayerState,
+    owner_id: &str,
     segments: &[Segment],
-    max_distance: f32,
-) -> Vec<(usize, f32)> {
-    let max_dist_sq = max_distance * max_distance;
-    let mut results = Vec::new();
+    death_radius: f32,
+) -> CollisionResult {
+    let mut nearest = CollisionResult::default();
+    for (index, segment) in segments.iter().enumerate() {
+        let mut result = check_trail_collision(player, std::slice::from_ref(segment), death_radius);
+        result.segment_index = result.segment_index.map(|_| index);
+        if beats(&result, &nearest) {
+            nearest = result;
+        }
+    }
+
+    if nearest.collided {
+        nearest.collision_type = Some(trail_collision_type(&player.id, owner_id));
+    }
+    nearest
+}
+
+/// Whether a bike has travelled far enough from spawn to arm its trail
+///
+/// The first trail segment starts right under a freshly spawned bike, so
+/// self-collision stays off until the bike is `death_radius` away.
+///
+/// # Arguments
+/// * `spawn_x`, `spawn_z` - Spawn point
+/// * `x`, `z` - Current position
+/// * `death_radius` - Collision threshold
+///
+/// # Returns
+/// True once the bike has cleared its spawn
+pub fn has_cleared_spawn(spawn_x: f32, spawn_z: f32, x: f32, z: f32, death_radius: f32) -> bool {
+    let dx = x - spawn_x;
+    let dz = z - spawn_z;
+    dx * dx + dz * dz >= death_radius * death_radius
+}
+
+/// Predicts how long until a bike hits a trail if it keeps its heading
+///
+/// Ray-marches the forward path in steps of half the death radius and
+/// returns the time of the first sample within the death radius of any
+/// segment. Drives the client proximity alarm.
+///
+/// # Arguments
+/// * `player` - Player state (position and heading)
+/// * `speed` - Current speed (units/sec)
+/// * `segments` - Trail segments to check against
+/// * `horizon` - How far ahead to look (seconds)
+///
+/// # Returns
+/// Seconds until collision, or None if the path is clear within `horizon`
+pub fn time_to_collision(
+    player: &PlayerState,
+    speed: f32,
+    segments: &[Segment],
+    horizon: f32,
+) -> Option<f32> {
+    let dir_len = (player.dir_x * player.dir_x + player.dir_z * player.dir_z).sqrt();
+    if speed <= 0.0 || horizon <= 0.0 || dir_len < EPS {
+        return None;
+    }
+
+    let dir_x = player.dir_x / dir_len;
+    let dir_z = player.dir_z / dir_len;
+    let death_radius = COLLISION_CONFIG.death_radius;
+    let step = death_radius * 0.5;
+    let max_distance = speed * horizon;
+
+    let mut travelled = 0.0;
+    while travelled <= max_distance {
+        let x = player.x + dir_x * travelled;
+        let z = player.z + dir_z * travelled;
+
+        let hit = segments.iter().any(|s| {
+            distance_to_segment(x, z, s.start_x, s.start_z, s.end_x, s.end_z) < death_radius
+        });
+        if hit {
+            return Some(travelled / speed);
+        }
+
+        travelled += step;
+    }
+
+    None
+}
+
+/// Calculates the unit normal of a segment facing a point
+///
+/// Returns the perpendicular on the same side of the segment as `point`,
+/// i.e. facing the side a bike approached from. Used for bounce responses
+/// and spark effects. A point exactly on the line gets the left-hand normal.
+/// For a degenerate segment the normal points from the segment toward
+/// `point` (or +X if they coincide).
+///
+/// # Arguments
+/// * `point` - Position of the approaching bike
+/// * `seg` - Segment that was hit
+///
+/// # Returns
+/// Unit normal vector
+pub fn segment_normal_at(point: &Vec2, seg: &Segment) -> Vec2 {
+    let dx = seg.end_x - seg.start_x;
+    let dz = seg.end_z - seg.start_z;
+    let len = (dx * dx + dz * dz).sqrt();
+    let to_x = point.x - seg.start_x;
+    let to_z = point.z - seg.start_z;
+
+    if len < EPS {
+        let to_len = (to_x * to_x + to_z * to_z).sqrt();
+        if to_len < EPS {
+            return Vec2 { x: 1.0, z: 0.0 };
+        }
+        return Vec2 { x: to_x / to_len, z: to_z / to_len };
+    }
+
+    // Left-hand perpendicular, flipped if the point is on the right
+    let (nx, nz) = (-dz / len, dx / len);
+    if to_x * nx + to_z * nz < 0.0 {
+        Vec2 { x: -nx, z: -nz }
+    } else {
+        Vec2 { x: nx, z: nz }
+    }
+}
+
+/// How two line segments meet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntersectionKind {
+    /// Segments properly cross each other
+    Cross,
+    /// Segments only touch (endpoint contact or collinear overlap)
+    Touch,
+    /// Segments do not meet
+    None,
+}
+
+/// Classifies how two line segments intersect
+///
+/// A proper crossing (each segment straddles the other) is a `Cross`.
+/// An endpoint resting on the other segment, such as a T-junction or
+/// shared endpoint, is a `Touch`, letting gameplay treat it as a near-miss.
+///
+/// # Arguments
+/// * `s1` - First segment
+/// * `s2` - Second segment
+///
+/// # Returns
+/// The kind of intersection
+pub fn classify_intersection(s1: &Segment, s2: &Segment) -> IntersectionKind {
+    classify_intersection_with_eps(s1, s2, EPS)
+}
+
+/// Classifies how two line segments intersect using a custom tolerance
+///
+/// Larger arenas can raise `eps` so that near-touches lost to float
+/// precision still classify as `Touch`.
+///
+/// # Arguments
+/// * `s1` - First segment
+/// * `s2` - Second segment
+/// * `eps` - Floating-point tolerance (see `CollisionConfig::eps`)
+///
+/// # Returns
+/// The kind of intersection
+pub fn classify_intersection_with_eps(s1: &Segment, s2: &Segment, eps: f32) -> IntersectionKind {
+    let d1 = direction(s2, &s1.start());
+    let d2 = direction(s2, &s1.end());
+    let d3 = direction(s1, &s2.start());
+    let d4 = direction(s1, &s2.end());
     
-    for (idx, segment) in segments.iter().enumerate() {
-        let dist_sq = distance_to_segment_squared(
-            px, pz,
-            segment.start_x, segment.start_z,
-            segment.end_x, segment.end_z,
-        );
-        
-        if dist_sq <= max_dist_sq {
-            results.push((idx, dist_sq.sqrt()));
+    // General case: segments straddle each other
+    if ((d1 > eps && d2 < -eps) || (d1 < -eps && d2 > eps))
+        && ((d3 > eps && d4 < -eps) || (d3 < -eps && d4 > eps))
+    {
+        return IntersectionKind::Cross;
+    }
+    
+    // Special cases: endpoints lie on the other segment
+    if (d1.abs() < eps && on_segment(s2, &s1.start(), eps))
+        || (d2.abs() < eps && on_segment(s2, &s1.end(), eps))
+        || (d3.abs() < eps && on_segment(s1, &s2.start(), eps))
+        || (d4.abs() < eps && on_segment(s1, &s2.end(), eps))
+    {
+        return IntersectionKind::Touch;
+    }
+    
+    IntersectionKind::None
+}
+
+/// Checks if two line segments intersect
+///
+/// Uses the cross product method to determine intersection.
+/// Both proper crossings and touches count; see `classify_intersection`
+/// to tell them apart.
+///
+/// # Arguments
+/// * `s1` - First segment
+/// * `s2` - Second segment
+///
+/// # Returns
+/// True if segments intersect
+pub fn segments_intersect(s1: &Segment, s2: &Segment) -> bool {
+    segments_intersect_with_eps(s1, s2, EPS)
+}
+
+/// Checks if two line segments intersect using a custom tolerance
+///
+/// # Arguments
+/// * `s1` - First segment
+/// * `s2` - Second segment
+/// * `eps` - Floating-point tolerance (see `CollisionConfig::eps`)
+///
+/// # Returns
+/// True if segments intersect
+pub fn segments_intersect_with_eps(s1: &Segment, s2: &Segment, eps: f32) -> bool {
+    classify_intersection_with_eps(s1, s2, eps) != IntersectionKind::None
+}
+
+/// Casts a ray against a segment
+///
+/// The ray starts at (ox, oz) and extends along (dx, dz) without limit;
+/// the direction need not be unit length, in which case `t` is measured in
+/// multiples of it. Rays parallel to the segment (including collinear
+/// ones) never hit.
+///
+/// # Arguments
+/// * `ox`, `oz` - Ray origin
+/// * `dx`, `dz` - Ray direction
+/// * `seg` - Segment to test
+///
+/// # Returns
+/// Ray parameter `t >= 0` of the hit point, or None
+pub fn ray_segment_intersection(ox: f32, oz: f32, dx: f32, dz: f32, seg: &Segment) -> Option<f32> {
+    let sx = seg.end_x - seg.start_x;
+    let sz = seg.end_z - seg.start_z;
+    let denom = dx * sz - dz * sx;
+    if denom.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let qx = seg.start_x - ox;
+    let qz = seg.start_z - oz;
+    let t = (qx * sz - qz * sx) / denom;
+    let u = (qx * dz - qz * dx) / denom;
+
+    (t >= 0.0 && (0.0..=1.0).contains(&u)).then_some(t)
+}
+
+/// Calculates the direction/cross product of three points
+///
+/// # Arguments
+/// * `s` - Segment to use as reference
+/// * `p` - Point to check (as (x, z) tuple)
+///
+/// # Returns
+/// Cross product value (positive = left, negative = right, zero = collinear)
+fn direction(s: &Segment, p: &(f32, f32)) -> f32 {
+    let (px, pz) = p;
+    let dx1 = px - s.start_x;
+    let dz1 = pz - s.start_z;
+    let dx2 = s.end_x - s.start_x;
+    let dz2 = s.end_z - s.start_z;
+    
+    dx1 * dz2 - dz1 * dx2
+}
+
+/// Checks if a point lies on a segment (assumes collinearity)
+///
+/// # Arguments
+/// * `s` - Segment
+/// * `p` - Point to check (as (x, z) tuple)
+/// * `eps` - Tolerance added to the segment's bounding box
+///
+/// # Returns
+/// True if point is on the segment
+fn on_segment(s: &Segment, p: &(f32, f32), eps: f32) -> bool {
+    let px = p.0;
+    let pz = p.1;
+    let min_x = s.start_x.min(s.end_x) - eps;
+    let max_x = s.start_x.max(s.end_x) + eps;
+    let min_z = s.start_z.min(s.end_z) - eps;
+    let max_z = s.start_z.max(s.end_z) + eps;
+    
+    (px >= min_x && px <= max_x) && (pz >= min_z && pz <= max_z)
+}
+
+impl Segment {
+    /// Get the start point as a tuple
+    pub fn start(&self) -> (f32, f32) {
+        (self.start_x, self.start_z)
+    }
+    
+    /// Get the end point as a tuple
+    pub fn end(&self) -> (f32, f32) {
+        (self.end_x, self.end_z)
+    }
+}
+
+/// Checks if a position is within arena bounds
+///
+/// # Arguments
+/// * `x`, `z` - Position to check
+/// * `arena_size` - Half-size of the arena (arena extends from -size to +size)
+///
+/// # Returns
+/// * `Ok(())` if within bounds
+/// * `Err` with position details if out of bounds
+pub fn check_arena_bounds(
+    x: f32, z: f32, arena_size: f32,
+) -> Result<(), crate::physics::PhysicsError> {
+    check_arena_bounds_with_config(x, z, arena_size, &COLLISION_CONFIG)
+}
+
+/// Checks if a position is within arena bounds using a custom config
+///
+/// Honors `config.wall_collision_dist`, matching `check_wall_collision`.
+///
+/// # Arguments
+/// * `x`, `z` - Position to check
+/// * `arena_size` - Half-size of the arena (arena extends from -size to +size)
+/// * `config` - Collision configuration
+///
+/// # Returns
+/// * `Ok(())` if within bounds
+/// * `Err` with position details if out of bounds
+pub fn check_arena_bounds_with_config(
+    x: f32, z: f32, arena_size: f32, config: &CollisionConfig,
+) -> Result<(), crate::physics::PhysicsError> {
+    let origin = Vec2 { x: 0.0, z: 0.0 };
+    let half_extents = Vec2 { x: arena_size, z: arena_size };
+    check_arena_bounds_at(x, z, &origin, &half_extents, config)
+}
+
+/// Checks if a position is within an arena placed anywhere on the map
+///
+/// The arena spans `center ± half_extents` on each axis; the origin-centered
+/// square of `check_arena_bounds_with_config` is the special case.
+///
+/// # Arguments
+/// * `x`, `z` - Position to check
+/// * `center` - Center of the arena
+/// * `half_extents` - Half-width (x) and half-depth (z) of the arena
+/// * `config` - Collision configuration (uses `wall_collision_dist`)
+///
+/// # Returns
+/// * `Ok(())` if within bounds
+/// * `Err` with position details if out of bounds; `arena_size` reports
+///   the larger half-extent
+pub fn check_arena_bounds_at(
+    x: f32, z: f32, center: &Vec2, half_extents: &Vec2, config: &CollisionConfig,
+) -> Result<(), crate::physics::PhysicsError> {
+    let dx = (x - center.x).abs();
+    let dz = (z - center.z).abs();
+
+    if dx > half_extents.x - config.wall_collision_dist || dz > half_extents.z - config.wall_collision_dist {
+        let arena_size = half_extents.x.max(half_extents.z);
+        Err(crate::physics::PhysicsError::OutOfBounds { x, z, arena_size })
+    } else {
+        Ok(())
+    }
+}
+
+/// Arena wall a bike can run into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WallSide {
+    /// The +z wall
+    North,
+    /// The -z wall
+    South,
+    /// The +x wall
+    East,
+    /// The -x wall
+    West,
+}
+
+impl WallSide {
+    /// Unit normal of the wall, pointing back into the arena
+    pub fn inward_normal(self) -> (f32, f32) {
+        match self {
+            WallSide::North => (0.0, -1.0),
+            WallSide::South => (0.0, 1.0),
+            WallSide::East => (-1.0, 0.0),
+            WallSide::West => (1.0, 0.0),
         }
     }
-    
-    results
-}
+}
+
+/// Which wall a position has breached, if any
+///
+/// Agrees with `check_arena_bounds`: returns `Some` exactly when that
+/// reports `OutOfBounds`. Past a corner the wall overshot by more wins;
+/// an exact diagonal reports the East/West wall.
+///
+/// # Arguments
+/// * `x`, `z` - Position to check
+/// * `arena_size` - Half-size of the arena
+///
+/// # Returns
+/// The breached wall, or None if the position is inside the arena
+pub fn which_wall(x: f32, z: f32, arena_size: f32) -> Option<WallSide> {
+    let bound = arena_size - COLLISION_CONFIG.wall_collision_dist;
+    let over_x = x.abs() - bound;
+    let over_z = z.abs() - bound;
+    if over_x <= 0.0 && over_z <= 0.0 {
+        return None;
+    }
+
+    Some(if over_x >= over_z {
+        if x > 0.0 { WallSide::East } else { WallSide::West }
+    } else if z > 0.0 {
+        WallSide::North
+    } else {
+        WallSide::South
+    })
+}
+
+/// Wraps a position into the arena, torus style
+///
+/// Each coordinate is brought into `[-arena_size, arena_size)`, so leaving
+/// one edge re-enters from the opposite edge.
+///
+/// # Arguments
+/// * `x`, `z` - Position to wrap
+/// * `arena_size` - Half-size of the arena
+///
+/// # Returns
+/// Tuple of wrapped (x, z)
+pub fn wrap_position(x: f32, z: f32, arena_size: f32) -> (f32, f32) {
+    let span = arena_size * 2.0;
+    let wrap = |v: f32| (v + arena_size).rem_euclid(span) - arena_size;
+    (wrap(x), wrap(z))
+}
+
+/// Applies the configured arena edge behavior to a position
+///
+/// # Arguments
+/// * `x`, `z` - Position reported by the bike
+/// * `arena_size` - Half-size of the arena
+/// * `config` - Collision configuration (uses `bounds_mode`)
+///
+/// # Returns
+/// * `Ok((x, z))` - the position to keep, wrapped in `BoundsMode::Wrap`
+/// * `Err(PhysicsError::OutOfBounds)` if the bike hit a wall
+pub fn resolve_bounds(
+    x: f32, z: f32, arena_size: f32, config: &CollisionConfig,
+) -> Result<(f32, f32), crate::physics::PhysicsError> {
+    match config.bounds_mode {
+        BoundsMode::Wall => check_arena_bounds_with_config(x, z, arena_size, config).map(|_| (x, z)),
+        BoundsMode::Wrap => Ok(wrap_position(x, z, arena_size)),
+    }
+}
+
+/// Whether a trail segment jumps across the seam of a wrapped arena
+///
+/// A bike that wraps lays a segment from one edge to the other. That
+/// segment was never driven, so trails in `BoundsMode::Wrap` should split
+/// at it rather than treat it as a wall. Real segments are never longer
+/// than half the arena in one tick.
+pub fn crosses_seam(segment: &Segment, arena_size: f32) -> bool {
+    (segment.end_x - segment.start_x).abs() > arena_size
+        || (segment.end_z - segment.start_z).abs() > arena_size
+}
+
+/// Checks for collision with arena walls
+///
+/// # Arguments
+/// * `x`, `z` - Position to check
+/// * `arena_size` - Half-size of the arena
+/// * `wall_distance` - Distance from edge to consider as collision
+///
+/// # Returns
+/// True if colliding with wall
+pub fn check_wall_collision(
+    x: f32, z: f32, arena_size: f32, wall_distance: f32,
+) -> bool {
+    let origin = Vec2 { x: 0.0, z: 0.0 };
+    let half_extents = Vec2 { x: arena_size, z: arena_size };
+    check_wall_collision_at(x, z, &origin, &half_extents, wall_distance)
+}
+
+/// Checks for collision with the walls of an arena placed anywhere
+///
+/// # Arguments
+/// * `x`, `z` - Position to check
+/// * `center` - Center of the arena
+/// * `half_extents` - Half-width (x) and half-depth (z) of the arena
+/// * `wall_distance` - Distance from edge to consider as collision
+///
+/// # Returns
+/// True if colliding with wall
+pub fn check_wall_collision_at(
+    x: f32, z: f32, center: &Vec2, half_extents: &Vec2, wall_distance: f32,
+) -> bool {
+    (x - center.x).abs() >= half_extents.x - wall_distance
+        || (z - center.z).abs() >= half_extents.z - wall_distance
+}
+
+/// Calculates how long until a bike reaches the arena boundary
+///
+/// Projects the bike along its heading at a constant speed to the first
+/// wall it would cross.
+///
+/// # Arguments
+/// * `player` - Player state (position and heading)
+/// * `speed` - Current speed (units/sec)
+/// * `arena_size` - Half-size of the arena
+///
+/// # Returns
+/// Seconds to impact, or None if the bike isn't moving toward any wall
+/// (stationary, or outside the arena and heading further out)
+pub fn time_to_wall(player: &PlayerState, speed: f32, arena_size: f32) -> Option<f32> {
+    if speed <= 0.0 {
+        return None;
+    }
+
+    [(player.x, player.dir_x), (player.z, player.dir_z)]
+        .into_iter()
+        .filter(|&(_, dir)| dir != 0.0)
+        .map(|(pos, dir)| (arena_size.copysign(dir) - pos) / (dir * speed))
+        .filter(|&t| t >= 0.0)
+        .min_by(f32::total_cmp)
+}
+
+/// Calculates the fraction of positions hugging an arena wall
+///
+/// Used by scoring and anti-stall logic to spot players who survive by
+/// riding along the edge of the arena.
+///
+/// # Arguments
+/// * `positions` - Recent positions of a player
+/// * `arena_size` - Half-size of the arena
+/// * `near_dist` - Distance from a wall that counts as hugging it
+///
+/// # Returns
+/// Fraction of positions near a wall (0.0 to 1.0, 0.0 if empty)
+pub fn wall_hug_fraction(positions: &[Vec2], arena_size: f32, near_dist: f32) -> f32 {
+    if positions.is_empty() {
+        return 0.0;
+    }
+    
+    let near_count = positions.iter()
+        .filter(|p| check_wall_collision(p.x, p.z, arena_size, near_dist))
+        .count();
+    
+    near_count as f32 / positions.len() as f32
+}
+
+/// Calculates the centroid of the alive pack
+///
+/// Used by the camera and sudden-death logic to center on the action
+/// rather than the arena origin.
+///
+/// # Arguments
+/// * `players` - All players
+///
+/// # Returns
+/// Mean position of alive players (origin if none are alive)
+pub fn pack_centroid(players: &[PlayerState]) -> Vec2 {
+    let alive: Vec<&PlayerState> = players.iter().filter(|p| p.alive).collect();
+    if alive.is_empty() {
+        return Vec2 { x: 0.0, z: 0.0 };
+    }
+
+    let n = alive.len() as f32;
+    Vec2 {
+        x: alive.iter().map(|p| p.x).sum::<f32>() / n,
+        z: alive.iter().map(|p| p.z).sum::<f32>() / n,
+    }
+}
+
+/// Calculates how spread out the alive pack is
+///
+/// # Arguments
+/// * `players` - All players
+/// * `centroid` - Pack centroid (see `pack_centroid`)
+///
+/// # Returns
+/// Largest distance from the centroid among alive players (0.0 if none)
+pub fn pack_spread(players: &[PlayerState], centroid: &Vec2) -> f32 {
+    players.iter()
+        .filter(|p| p.alive)
+        .map(|p| ((p.x - centroid.x).powi(2) + (p.z - centroid.z).powi(2)).sqrt())
+        .fold(0.0, f32::max)
+}
+
+/// Checks for slipstream effect from another player
+///
+/// # Arguments
+/// * `player` - Player to check slipstream for
+/// * `leader` - Potential slipstream leader
+/// * `slipstream_distance` - Maximum distance for slipstream
+/// * `slipstream_angle` - Maximum angle for slipstream (radians)
+///
+/// # Returns
+/// True if player is in slipstream of leader
+pub fn check_slipstream(
+    player: &PlayerState,
+    leader: &PlayerState,
+    slipstream_distance: f32,
+    slipstream_angle: f32,
+) -> bool {
+    slipstream_within(
+        player,
+        leader,
+        slipstream_distance * slipstream_distance,
+        slipstream_angle,
+    )
+}
+
+/// Checks for slipstream effect using a collision configuration
+///
+/// Uses the precomputed squared slipstream distance from the config.
+///
+/// # Arguments
+/// * `player` - Player to check slipstream for
+/// * `leader` - Potential slipstream leader
+/// * `config` - Collision configuration
+///
+/// # Returns
+/// True if player is in slipstream of leader
+pub fn check_slipstream_with_config(
+    player: &PlayerState,
+    leader: &PlayerState,
+    config: &CollisionConfig,
+) -> bool {
+    slipstream_within(
+        player,
+        leader,
+        config.slipstream_distance_squared(),
+        config.slipstream_angle,
+    )
+}
+
+/// Shared slipstream test against a squared distance threshold
+fn slipstream_within(
+    player: &PlayerState,
+    leader: &PlayerState,
+    slipstream_distance_sq: f32,
+    slipstream_angle: f32,
+) -> bool {
+    // Vector from player to leader
+    let dx = leader.x - player.x;
+    let dz = leader.z - player.z;
+    let dist_sq = dx * dx + dz * dz;
+    
+    // Check distance
+    if dist_sq > slipstream_distance_sq {
+        return false;
+    }
+    
+    // Check angle (player should be facing toward leader)
+    let dist = dist_sq.sqrt();
+    if dist < EPS {
+        return false;
+    }
+    
+    // Normalize direction to leader
+    let to_leader_x = dx / dist;
+    let to_leader_z = dz / dist;
+    
+    // Dot product with player direction
+    let dot = player.dir_x * to_leader_x + player.dir_z * to_leader_z;
+    
+    // Check if angle is within slipstream cone
+    dot > slipstream_angle.cos()
+}
+
+/// Speed bonus for drafting directly behind another bike
+pub const SLIPSTREAM_BONUS: f32 = 0.1;
+
+/// Factor applied to the bonus for each further link in a draft chain
+pub const SLIPSTREAM_CHAIN_FALLOFF: f32 = 0.5;
+
+/// Cap on the total chained slipstream bonus
+pub const SLIPSTREAM_MAX_BONUS: f32 = 0.15;
+
+/// Resolves draft chains across a pack and returns speed multipliers
+///
+/// Each alive bike drafts the nearest bike in its slipstream cone. A bike
+/// earns `SLIPSTREAM_BONUS` for its own draft, plus a diminishing share for
+/// each bike further up the chain, capped at `SLIPSTREAM_MAX_BONUS`.
+///
+/// # Arguments
+/// * `players` - All player states
+/// * `config` - Collision configuration (slipstream distance and angle)
+///
+/// # Returns
+/// Map of player id to speed multiplier (1.0 = no bonus)
+pub fn compute_slipstream_chain(
+    players: &[PlayerState],
+    config: &CollisionConfig,
+) -> HashMap<String, f32> {
+    // Index of the bike each player is drafting, if any
+    let drafting: Vec<Option<usize>> = players
+        .iter()
+        .enumerate()
+        .map(|(i, player)| {
+            if !player.alive {
+                return None;
+            }
+            players
+                .iter()
+                .enumerate()
+                .filter(|(j, leader)| {
+                    *j != i && leader.alive && check_slipstream_with_config(player, leader, config)
+                })
+                .map(|(j, leader)| {
+                    let dist_sq = (leader.x - player.x).powi(2) + (leader.z - player.z).powi(2);
+                    (j, dist_sq)
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)))
+                .map(|(j, _)| j)
+        })
+        .collect();
+
+    let mut multipliers = HashMap::with_capacity(players.len());
+    for (i, player) in players.iter().enumerate() {
+        let mut bonus = 0.0;
+        let mut link_bonus = SLIPSTREAM_BONUS;
+        let mut visited = vec![false; players.len()];
+        let mut current = i;
+        visited[i] = true;
+
+        // Walk up the chain; stop at the front of the pack or on a loop
+        while let Some(leader) = drafting[current] {
+            if visited[leader] {
+                break;
+            }
+            visited[leader] = true;
+            bonus += link_bonus;
+            link_bonus *= SLIPSTREAM_CHAIN_FALLOFF;
+            current = leader;
+        }
+
+        multipliers.insert(player.id.clone(), 1.0 + bonus.min(SLIPSTREAM_MAX_BONUS));
+    }
+
+    multipliers
+}
+
+/// Finds the closest segment to a point
+///
+/// Ties are deterministic: a later segment only wins if it is closer by
+/// more than `EPS`, so segments equidistant within float error always
+/// resolve to the lowest index. AI decisions and replays rely on this.
+///
+/// # Arguments
+/// * `px`, `pz` - Point to check
+/// * `segments` - Segments to search
+///
+/// # Returns
+/// Tuple of (segment_index, distance) or None if no segments
+pub fn find_closest_segment(
+    px: f32, pz: f32,
+    segments: &[Segment],
+) -> Option<(usize, f32)> {
+    if segments.is_empty() {
+        return None;
+    }
+    
+    let mut closest_idx = 0;
+    let mut closest_dist = f32::MAX;
+    
+    for (idx, segment) in segments.iter().enumerate() {
+        let dist = distance_to_segment_struct(px, pz, segment);
+        if dist < closest_dist - EPS {
+            closest_dist = dist;
+            closest_idx = idx;
+        }
+    }
+    
+    Some((closest_idx, closest_dist))
+}
+
+/// Smallest cell size `optimal_cell_size` will return (units)
+pub const MIN_GRID_CELL_SIZE: f32 = 4.0;
+
+/// Average number of segments a grid cell should hold
+const SEGMENTS_PER_CELL: f32 = 4.0;
+
+/// Picks a spatial grid cell size for the current trails
+///
+/// Aims for about `SEGMENTS_PER_CELL` segments per cell, so dense arenas
+/// get finer cells, but never goes below the average segment length
+/// (long segments would otherwise be registered in many cells). The
+/// result stays between `MIN_GRID_CELL_SIZE` and `arena_size`.
+///
+/// # Arguments
+/// * `segments` - All trail segments
+/// * `arena_size` - Half-size of the arena
+///
+/// # Returns
+/// Cell width (units)
+pub fn optimal_cell_size(segments: &[Segment], arena_size: f32) -> f32 {
+    let max_size = arena_size.max(MIN_GRID_CELL_SIZE);
+    if segments.is_empty() {
+        return max_size;
+    }
+
+    let count = segments.len() as f32;
+    let avg_length = segments.iter().map(Segment::length).sum::<f32>() / count;
+    let area = (arena_size * 2.0).powi(2);
+    let density_size = (area * SEGMENTS_PER_CELL / count).sqrt();
+
+    density_size.max(avg_length).clamp(MIN_GRID_CELL_SIZE, max_size)
+}
+
+/// Gets all segments within a certain distance of a point
+///
+/// # Arguments
+/// * `px`, `pz` - Point to check
+/// * `segments` - Segments to search
+/// * `max_distance` - Maximum distance threshold
+///
+/// # Returns
+/// Vector of (segment_index, distance) tuples
+pub fn find_segments_within_distance(
+    px: f32, pz: f32,
+    segments: &[Segment],
+    max_distance: f32,
+) -> Vec<(usize, f32)> {
+    let mut scratch = CollisionScratch::default();
+    find_segments_within_distance_into(&mut scratch, px, pz, segments, max_distance);
+    scratch.nearby
+}
+
+/// Allocation-free form of `find_segments_within_distance`
+///
+/// Results replace the contents of `scratch.nearby`.
+///
+/// # Returns
+/// The refilled `scratch.nearby`
+pub fn find_segments_within_distance_into<'a>(
+    scratch: &'a mut CollisionScratch,
+    px: f32, pz: f32,
+    segments: &[Segment],
+    max_distance: f32,
+) -> &'a [(usize, f32)] {
+    let max_dist_sq = max_distance * max_distance;
+    scratch.nearby.clear();
+    
+    for (idx, segment) in segments.iter().enumerate() {
+        let dist_sq = distance_to_segment_squared(
+            px, pz,
+            segment.start_x, segment.start_z,
+            segment.end_x, segment.end_z,
+        );
+        
+        if dist_sq <= max_dist_sq {
+            scratch.nearby.push((idx, dist_sq.sqrt()));
+        }
+    }
+    
+    &scratch.nearby
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eps_constant() {
+        assert_eq!(EPS, 0.01);
+    }
+
+    #[test]
+    fn test_segment_new() {
+        let seg = Segment::new(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(seg.start_x, 0.0);
+        assert_eq!(seg.start_z, 0.0);
+        assert_eq!(seg.end_x, 10.0);
+        assert_eq!(seg.end_z, 10.0);
+    }
+
+    #[test]
+    fn test_segment_from_positions() {
+        let seg = Segment::from_positions(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(seg.start_x, 1.0);
+        assert_eq!(seg.start_z, 2.0);
+        assert_eq!(seg.end_x, 3.0);
+        assert_eq!(seg.end_z, 4.0);
+    }
+
+    #[test]
+    fn test_segment_length() {
+        let seg = Segment::new(0.0, 0.0, 3.0, 4.0);
+        assert!((seg.length() - 5.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_segment_length_zero() {
+        let seg = Segment::new(1.0, 1.0, 1.0, 1.0);
+        assert!((seg.length() - 0.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_distance_to_segment_point_on_start() {
+        let dist = distance_to_segment(0.0, 0.0, 0.0, 0.0, 10.0, 0.0);
+        assert!((dist - 0.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_distance_to_segment_point_on_end() {
+        let dist = distance_to_segment(10.0, 0.0, 0.0, 0.0, 10.0, 0.0);
+        assert!((dist - 0.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_distance_to_segment_point_on_line() {
+        let dist = distance_to_segment(5.0, 0.0, 0.0, 0.0, 10.0, 0.0);
+        assert!((dist - 0.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_distance_to_segment_perpendicular() {
+        let dist = distance_to_segment(5.0, 3.0, 0.0, 0.0, 10.0, 0.0);
+        assert!((dist - 3.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_distance_to_segment_beyond_start() {
+        let dist = distance_to_segment(-5.0, 0.0, 0.0, 0.0, 10.0, 0.0);
+        assert!((dist - 5.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_distance_to_segment_beyond_end() {
+        let dist = distance_to_segment(15.0, 0.0, 0.0, 0.0, 10.0, 0.0);
+        assert!((dist - 5.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_distance_to_segment_degenerate() {
+        let dist = distance_to_segment(3.0, 4.0, 0.0, 0.0, 0.0, 0.0);
+        assert!((dist - 5.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_distance_to_segment_squared() {
+        let dist_sq = distance_to_segment_squared(3.0, 4.0, 0.0, 0.0, 10.0, 0.0);
+        assert!((dist_sq - 16.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_distance_to_segment_struct() {
+        let seg = Segment::new(0.0, 0.0, 10.0, 0.0);
+        let dist = distance_to_segment_struct(5.0, 3.0, &seg);
+        assert!((dist - 3.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_player_state_new() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        assert_eq!(player.id, "p1");
+        assert_eq!(player.x, 0.0);
+        assert_eq!(player.z, 0.0);
+        assert_eq!(player.dir_x, 1.0);
+        assert_eq!(player.dir_z, 0.0);
+        assert!(player.alive);
+    }
+
+    #[test]
+    fn test_collision_result_default() {
+        let result = CollisionResult::default();
+        assert!(!result.collided);
+        assert!(result.collision_type.is_none());
+        assert_eq!(result.distance, f32::MAX);
+        assert!(result.segment_index.is_none());
+    }
+
+    #[test]
+    fn test_check_trail_collision_no_collision() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 10.0, 0.0, 1.0, true);
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        
+        let result = check_trail_collision(&player, &segments, 2.0);
+        assert!(!result.collided);
+    }
+
+    #[test]
+    fn test_check_trail_collision_hit() {
+        let player = PlayerState::new("p1".to_string(), 5.0, 0.5, 0.0, 1.0, true);
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        
+        let result = check_trail_collision(&player, &segments, 2.0);
+        assert!(result.collided);
+        assert_eq!(result.segment_index, Some(0));
+    }
+
+    #[test]
+    fn test_check_trail_collision_dead_player() {
+        let player = PlayerState::new("p1".to_string(), 5.0, 0.0, 0.0, 1.0, false);
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        
+        let result = check_trail_collision(&player, &segments, 2.0);
+        assert!(!result.collided);
+    }
+
+    #[test]
+    fn test_check_trail_collision_with_owner_self() {
+        let player = PlayerState::new("p1".to_string(), 5.0, 0.5, 0.0, 1.0, true);
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        
+        let result = check_trail_collision_with_owner(&player, "p1", &segments, 2.0);
+        assert!(result.collided);
+        assert_eq!(result.collision_type, Some(CollisionType::SelfTrail));
+    }
+
+    #[test]
+    fn test_check_trail_collision_with_owner_other() {
+        let player = PlayerState::new("p1".to_string(), 5.0, 0.5, 0.0, 1.0, true);
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        
+        let result = check_trail_collision_with_owner(&player, "p2", &segments, 2.0);
+        assert!(result.collided);
+        assert_eq!(result.collision_type, Some(CollisionType::OtherTrail("p2".to_string())));
+    }
+
+    #[test]
+    fn test_continuous_collision_check_intersect() {
+        let segments = [Segment::new(0.0, 0.0, 10.0, 10.0)];
+        
+        // Movement crosses the segment
+        let result = continuous_collision_check(0.0, 10.0, 10.0, 0.0, &segments);
+        assert!(result.collided);
+    }
+
+    #[test]
+    fn test_continuous_collision_check_no_intersect() {
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        
+        // Movement parallel and away from segment
+        let result = continuous_collision_check(0.0, 5.0, 10.0, 5.0, &segments);
+        assert!(!result.collided);
+    }
+
+    #[test]
+    fn test_continuous_collision_check_stationary_on_trail() {
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        
+        // Bike hasn't moved and sits on the trail
+        let result = continuous_collision_check(5.0, 0.5, 5.0, 0.5, &segments);
+        assert!(result.collided);
+        assert_eq!(result.segment_index, Some(0));
+    }
+
+    #[test]
+    fn test_continuous_collision_check_stationary_clear() {
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        
+        // Bike hasn't moved and sits well clear of the trail
+        let result = continuous_collision_check(5.0, 10.0, 5.0, 10.0, &segments);
+        assert!(!result.collided);
+    }
+
+    #[test]
+    fn test_continuous_collision_check_empty_segments() {
+        let moving = continuous_collision_check(0.0, 0.0, 10.0, 0.0, &[]);
+        assert_eq!(moving, CollisionResult::default());
+
+        let stationary = continuous_collision_check(5.0, 5.0, 5.0, 5.0, &[]);
+        assert_eq!(stationary, CollisionResult::default());
+    }
+
+    #[test]
+    fn test_continuous_collision_check_single_segment() {
+        let segments = [Segment::new(5.0, -5.0, 5.0, 5.0)];
+
+        let hit = continuous_collision_check(0.0, 0.0, 10.0, 0.0, &segments);
+        assert!(hit.collided);
+        assert_eq!(hit.segment_index, Some(0));
+
+        let miss = continuous_collision_check(0.0, 0.0, 3.0, 0.0, &segments);
+        assert!(!miss.collided);
+        assert_eq!(miss.segment_index, None);
+    }
+
+    #[test]
+    fn test_continuous_collision_check_stationary_miss_has_no_index() {
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        let result = continuous_collision_check(5.0, 20.0, 5.0, 20.0, &segments);
+
+        assert!(!result.collided);
+        assert_eq!(result.segment_index, None);
+    }
+
+    #[test]
+    fn test_segments_intersect_cross() {
+        let s1 = Segment::new(0.0, 0.0, 10.0, 10.0);
+        let s2 = Segment::new(0.0, 10.0, 10.0, 0.0);
+        
+        assert!(segments_intersect(&s1, &s2));
+    }
+
+    #[test]
+    fn test_segments_intersect_parallel() {
+        let s1 = Segment::new(0.0, 0.0, 10.0, 0.0);
+        let s2 = Segment::new(0.0, 1.0, 10.0, 1.0);
+        
+        assert!(!segments_intersect(&s1, &s2));
+    }
+
+    #[test]
+    fn test_segments_intersect_touch_endpoints() {
+        let s1 = Segment::new(0.0, 0.0, 5.0, 5.0);
+        let s2 = Segment::new(5.0, 5.0, 10.0, 0.0);
+        
+        assert!(segments_intersect(&s1, &s2));
+    }
+
+    #[test]
+    fn test_segments_intersect_collinear_overlap() {
+        let s1 = Segment::new(0.0, 0.0, 10.0, 0.0);
+        let s2 = Segment::new(5.0, 0.0, 15.0, 0.0);
+        
+        assert!(segments_intersect(&s1, &s2));
+    }
+
+    #[test]
+    fn test_classify_intersection_cross() {
+        let s1 = Segment::new(0.0, 0.0, 10.0, 10.0);
+        let s2 = Segment::new(0.0, 10.0, 10.0, 0.0);
+        
+        assert_eq!(classify_intersection(&s1, &s2), IntersectionKind::Cross);
+    }
+
+    #[test]
+    fn test_classify_intersection_t_junction() {
+        let s1 = Segment::new(0.0, 0.0, 10.0, 0.0);
+        let s2 = Segment::new(5.0, 0.0, 5.0, 10.0);
+        
+        assert_eq!(classify_intersection(&s1, &s2), IntersectionKind::Touch);
+        assert!(segments_intersect(&s1, &s2));
+    }
+
+    #[test]
+    fn test_classify_intersection_disjoint() {
+        let s1 = Segment::new(0.0, 0.0, 10.0, 0.0);
+        let s2 = Segment::new(0.0, 5.0, 10.0, 15.0);
+        
+        assert_eq!(classify_intersection(&s1, &s2), IntersectionKind::None);
+        assert!(!segments_intersect(&s1, &s2));
+    }
+
+    #[test]
+    fn test_check_arena_bounds_inside() {
+        let result = check_arena_bounds(50.0, 50.0, 100.0);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_check_arena_bounds_outside() {
+        let result = check_arena_bounds(150.0, 50.0, 100.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_check_arena_bounds_edge() {
+        let result = check_arena_bounds(99.0, 50.0, 100.0);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_check_arena_bounds_custom_wall_distance() {
+        let wide = CollisionConfig { wall_collision_dist: 10.0, ..Default::default() };
+
+        // 95 is inside the default bound (99) but past the custom one (90)
+        assert!(check_arena_bounds(95.0, 0.0, 100.0).is_ok());
+        assert!(check_arena_bounds_with_config(95.0, 0.0, 100.0, &wide).is_err());
+        assert!(check_arena_bounds_with_config(89.0, 0.0, 100.0, &wide).is_ok());
+    }
+
+    #[test]
+    fn test_check_arena_bounds_off_center() {
+        // 100x60 arena centered on (300, -50)
+        let center = Vec2 { x: 300.0, z: -50.0 };
+        let half = Vec2 { x: 50.0, z: 30.0 };
+        let config = CollisionConfig::default();
+
+        assert!(check_arena_bounds_at(300.0, -50.0, &center, &half, &config).is_ok());
+        assert!(check_arena_bounds_at(340.0, -25.0, &center, &half, &config).is_ok());
+        // The origin is nowhere near this arena
+        assert!(check_arena_bounds_at(0.0, 0.0, &center, &half, &config).is_err());
+        // Past the shorter z extent even though x is fine
+        assert!(check_arena_bounds_at(300.0, -15.0, &center, &half, &config).is_err());
+        assert!(check_arena_bounds_at(355.0, -50.0, &center, &half, &config).is_err());
+    }
+
+    #[test]
+    fn test_check_wall_collision_off_center() {
+        let center = Vec2 { x: -80.0, z: 20.0 };
+        let half = Vec2 { x: 40.0, z: 40.0 };
+
+        assert!(!check_wall_collision_at(-80.0, 20.0, &center, &half, 5.0));
+        assert!(check_wall_collision_at(-117.0, 20.0, &center, &half, 5.0));
+        assert!(check_wall_collision_at(-80.0, 56.0, &center, &half, 5.0));
+        // Centered at the origin matches the legacy check
+        let origin = Vec2 { x: 0.0, z: 0.0 };
+        let square = Vec2 { x: 100.0, z: 100.0 };
+        for &(x, z) in &[(50.0, 50.0), (96.0, 0.0), (0.0, -99.0)] {
+            assert_eq!(
+                check_wall_collision_at(x, z, &origin, &square, 5.0),
+                check_wall_collision(x, z, 100.0, 5.0),
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_wall_collision_safe() {
+        assert!(!check_wall_collision(50.0, 50.0, 100.0, 5.0));
+    }
+
+    #[test]
+    fn test_check_wall_collision_hit() {
+        assert!(check_wall_collision(98.0, 50.0, 100.0, 5.0));
+    }
+
+    #[test]
+    fn test_wall_hug_fraction_along_wall() {
+        let positions: Vec<Vec2> = (0..20)
+            .map(|i| Vec2 { x: 98.0, z: -90.0 + i as f32 * 9.0 })
+            .collect();
+        
+        let fraction = wall_hug_fraction(&positions, 100.0, 5.0);
+        assert!((fraction - 1.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_wall_hug_fraction_through_center() {
+        let positions: Vec<Vec2> = (0..20)
+            .map(|i| Vec2 { x: -50.0 + i as f32 * 5.0, z: 0.0 })
+            .collect();
+        
+        assert_eq!(wall_hug_fraction(&positions, 100.0, 5.0), 0.0);
+    }
+
+    #[test]
+    fn test_wall_hug_fraction_empty() {
+        assert_eq!(wall_hug_fraction(&[], 100.0, 5.0), 0.0);
+    }
+
+    #[test]
+    fn test_check_slipstream_behind() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 0.0, 1.0, true);
+        let leader = PlayerState::new("p2".to_string(), 0.0, 3.0, 0.0, 1.0, true);
+        
+        assert!(check_slipstream(&player, &leader, 5.0, 0.3));
+    }
+
+    #[test]
+    fn test_check_slipstream_too_far() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 0.0, 1.0, true);
+        let leader = PlayerState::new("p2".to_string(), 0.0, 10.0, 0.0, 1.0, true);
+        
+        assert!(!check_slipstream(&player, &leader, 5.0, 0.3));
+    }
+
+    #[test]
+    fn test_check_slipstream_wrong_angle() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let leader = PlayerState::new("p2".to_string(), 0.0, 3.0, 0.0, 1.0, true);
+        
+        assert!(!check_slipstream(&player, &leader, 5.0, 0.3));
+    }
+
+    #[test]
+    fn test_check_slipstream_with_config() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 0.0, 1.0, true);
+        let near = PlayerState::new("p2".to_string(), 0.0, 3.0, 0.0, 1.0, true);
+        let far = PlayerState::new("p3".to_string(), 0.0, 10.0, 0.0, 1.0, true);
+        
+        assert!(check_slipstream_with_config(&player, &near, &COLLISION_CONFIG));
+        assert!(!check_slipstream_with_config(&player, &far, &COLLISION_CONFIG));
+    }
+
+    #[test]
+    fn test_find_closest_segment() {
+        let segments = [
+            Segment::new(0.0, 0.0, 10.0, 0.0),
+            Segment::new(0.0, 10.0, 10.0, 10.0),
+        ];
+        
+        let result = find_closest_segment(5.0, 1.0, &segments);
+        assert_eq!(result.unwrap().0, 0);
+    }
+
+    #[test]
+    fn test_find_closest_segment_empty() {
+        let segments: [Segment; 0] = [];
+        assert!(find_closest_segment(0.0, 0.0, &segments).is_none());
+    }
+
+    #[test]
+    fn test_find_segments_within_distance() {
+        let segments = [
+            Segment::new(0.0, 0.0, 10.0, 0.0),
+            Segment::new(0.0, 10.0, 10.0, 10.0),
+        ];
+        
+        let results = find_segments_within_distance(5.0, 1.0, &segments, 3.0);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 0);
+    }
+
+    #[test]
+    fn test_collision_config_constants() {
+        assert_eq!(COLLISION_CONFIG.death_radius, 2.0);
+        assert_eq!(COLLISION_CONFIG.bike_collision_dist, 3.0);
+        assert_eq!(COLLISION_CONFIG.trail_collision_dist, 2.5);
+    }
+
+    #[test]
+    fn test_collision_stats_record_mix() {
+        let mut stats = CollisionStats::default();
+        stats.record_collision(&CollisionType::Wall);
+        stats.record_collision(&CollisionType::SelfTrail);
+        stats.record_collision(&CollisionType::OtherTrail("p2".to_string()));
+        stats.record_collision(&CollisionType::OtherTrail("p3".to_string()));
+        stats.record_collision(&CollisionType::Wall);
+        stats.record_collision(&CollisionType::Wall);
+        
+        assert_eq!(stats.wall, 3);
+        assert_eq!(stats.self_trail, 1);
+        assert_eq!(stats.other_trail, 2);
+        assert_eq!(stats.total(), 6);
+    }
+
+    #[test]
+    fn test_collision_type_debug() {
+        let self_trail = CollisionType::SelfTrail;
+        assert_eq!(format!("{:?}", self_trail), "SelfTrail");
+        
+        let other = CollisionType::OtherTrail("p2".to_string());
+        assert!(format!("{:?}", other).contains("p2"));
+        
+        let wall = CollisionType::Wall;
+        assert_eq!(format!("{:?}", wall), "Wall");
+    }
+
+    #[test]
+    fn test_time_to_collision_head_on() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let segments = [Segment::new(10.0, -20.0, 10.0, 20.0)];
+
+        // Trail is 10 units ahead; at 40 u/s the bike reaches the death radius in ~0.2s
+        let time = time_to_collision(&player, 40.0, &segments, 2.0).unwrap();
+        assert!(time > 0.15 && time < 0.25);
+    }
+
+    #[test]
+    fn test_time_to_collision_clear_path() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let segments = [Segment::new(-10.0, -20.0, -10.0, 20.0)];
+
+        assert_eq!(time_to_collision(&player, 40.0, &segments, 2.0), None);
+    }
+
+    #[test]
+    fn test_time_to_collision_beyond_horizon() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let segments = [Segment::new(100.0, -20.0, 100.0, 20.0)];
+
+        assert_eq!(time_to_collision(&player, 40.0, &segments, 1.0), None);
+        assert!(time_to_collision(&player, 40.0, &segments, 3.0).is_some());
+    }
+
+    #[test]
+    fn test_time_to_collision_stationary() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let segments = [Segment::new(10.0, -20.0, 10.0, 20.0)];
+
+        assert_eq!(time_to_collision(&player, 0.0, &segments, 2.0), None);
+    }
+
+    fn crossing_scenario() -> (Vec<PlayerState>, Vec<(String, Vec<Segment>)>) {
+        let players = vec![
+            // p1 is driving into p2's trail
+            PlayerState::new("p1".to_string(), 10.0, 0.5, 1.0, 0.0, true),
+            // p2 is clear of everything
+            PlayerState::new("p2".to_string(), 10.0, 50.0, 0.0, 1.0, true),
+            // p3 has looped back onto its own trail
+            PlayerState::new("p3".to_string(), -30.0, 0.0, 0.0, -1.0, true),
+            // p4 is already dead on p2's trail
+            PlayerState::new("p4".to_string(), 10.0, 10.0, 1.0, 0.0, false),
+        ];
+        let trails = vec![
+            ("p1".to_string(), vec![Segment::new(-20.0, 0.5, 10.0, 0.5)]),
+            ("p2".to_string(), vec![
+                Segment::new(10.0, -20.0, 10.0, 20.0),
+                Segment::new(10.0, 20.0, 10.0, 50.0),
+            ]),
+            ("p3".to_string(), vec![
+                Segment::new(-40.0, 0.0, -20.0, 0.0),
+                Segment::new(-20.0, 0.0, -20.0, 20.0),
+                Segment::new(-20.0, 20.0, -30.0, 20.0),
+                Segment::new(-30.0, 20.0, -30.0, 0.0),
+            ]),
+        ];
+        (players, trails)
+    }
+
+    #[test]
+    fn test_check_all_collisions_multi_player() {
+        let (players, trails) = crossing_scenario();
+        let results = check_all_collisions(&players, &trails, &CollisionConfig::default());
+
+        // Dead players are skipped
+        assert_eq!(results.len(), 3);
+
+        let (id, p1) = &results[0];
+        assert_eq!(id, "p1");
+        assert!(p1.collided);
+        assert_eq!(p1.collision_type, Some(CollisionType::OtherTrail("p2".to_string())));
+
+        let (id, p2) = &results[1];
+        assert_eq!(id, "p2");
+        assert!(!p2.collided);
+
+        let (id, p3) = &results[2];
+        assert_eq!(id, "p3");
+        assert!(p3.collided);
+        assert_eq!(p3.collision_type, Some(CollisionType::SelfTrail));
+    }
+
+    #[test]
+    fn test_check_all_collisions_ignores_current_own_segment() {
+        let players = [PlayerState::new("p1".to_string(), 10.0, 0.0, 1.0, 0.0, true)];
+        let trails = vec![("p1".to_string(), vec![Segment::new(0.0, 0.0, 10.0, 0.0)])];
+
+        let results = check_all_collisions(&players, &trails, &CollisionConfig::default());
+        assert!(!results[0].1.collided);
+    }
+
+    #[test]
+    fn test_classify_intersection_larger_eps_touches() {
+        // s1 stops 0.05 units short of s2
+        let s1 = Segment::new(0.0, 0.0, 10.0, 0.0);
+        let s2 = Segment::new(10.05, -5.0, 10.05, 5.0);
+
+        assert_eq!(classify_intersection(&s1, &s2), IntersectionKind::None);
+        assert_eq!(classify_intersection_with_eps(&s1, &s2, 1.0), IntersectionKind::Touch);
+        assert!(segments_intersect_with_eps(&s1, &s2, 1.0));
+        assert!(!segments_intersect(&s1, &s2));
+    }
+
+    #[test]
+    fn test_distance_to_segment_squared_with_eps_degenerate() {
+        // A 0.5-unit segment is a real segment by default but a point with eps = 1.0
+        let default = distance_to_segment_squared(0.5, 1.0, 0.0, 0.0, 0.5, 0.0);
+        let coarse = distance_to_segment_squared_with_eps(0.5, 1.0, 0.0, 0.0, 0.5, 0.0, 1.0);
+
+        assert!((default - 1.0).abs() < EPS);
+        assert!((coarse - 1.25).abs() < EPS);
+    }
+
+    #[test]
+    fn test_check_trail_collision_grown_bike() {
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+        let normal = PlayerState::new("p1".to_string(), 5.0, 3.0, 1.0, 0.0, true);
+        let grown = normal.clone().with_radius(1.5);
+
+        assert_eq!(normal.radius, 0.0);
+        assert!(!check_trail_collision(&normal, &segments, 2.0).collided);
+        assert!(check_trail_collision(&grown, &segments, 2.0).collided);
+    }
+
+    #[test]
+    fn test_slipstream_chain_three_bike_line() {
+        let players = [
+            PlayerState::new("front".to_string(), 8.0, 0.0, 1.0, 0.0, true),
+            PlayerState::new("middle".to_string(), 4.0, 0.0, 1.0, 0.0, true),
+            PlayerState::new("rear".to_string(), 0.0, 0.0, 1.0, 0.0, true),
+        ];
+        let chain = compute_slipstream_chain(&players, &CollisionConfig::default());
+
+        assert_eq!(chain["front"], 1.0);
+        assert!((chain["middle"] - (1.0 + SLIPSTREAM_BONUS)).abs() < EPS);
+        let chained = 1.0 + SLIPSTREAM_BONUS * (1.0 + SLIPSTREAM_CHAIN_FALLOFF);
+        assert!((chain["rear"] - chained).abs() < EPS);
+        assert!(chain["rear"] > chain["middle"]);
+    }
+
+    #[test]
+    fn test_slipstream_chain_isolated_bikes() {
+        let players = [
+            PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true),
+            PlayerState::new("p2".to_string(), 50.0, 50.0, 0.0, 1.0, true),
+            PlayerState::new("p3".to_string(), -50.0, 20.0, -1.0, 0.0, true),
+        ];
+        let chain = compute_slipstream_chain(&players, &CollisionConfig::default());
+
+        assert_eq!(chain.len(), 3);
+        assert!(chain.values().all(|&m| m == 1.0));
+    }
+
+    #[test]
+    fn test_slipstream_chain_capped() {
+        let players: Vec<PlayerState> = (0..8)
+            .map(|i| PlayerState::new(format!("p{}", i), i as f32 * 4.0, 0.0, 1.0, 0.0, true))
+            .collect();
+        let chain = compute_slipstream_chain(&players, &CollisionConfig::default());
+
+        assert!(chain.values().all(|&m| m <= 1.0 + SLIPSTREAM_MAX_BONUS));
+        assert_eq!(chain["p0"], 1.0 + SLIPSTREAM_MAX_BONUS);
+    }
+
+    #[test]
+    fn test_find_closest_segment_tie_prefers_lower_index() {
+        // Equidistant within float error on either side of the point
+        let segments = [
+            Segment::new(-10.0, 5.0, 10.0, 5.0),
+            Segment::new(-10.0, -5.000_01, 10.0, -5.000_01),
+            Segment::new(-10.0, 4.999_99, 10.0, 4.999_99),
+        ];
+
+        let (idx, dist) = find_closest_segment(0.0, 0.0, &segments).unwrap();
+        assert_eq!(idx, 0);
+        assert!((dist - 5.0).abs() < EPS);
+    }
+
+    fn spawn_stub_trail() -> Vec<(String, Vec<Segment>)> {
+        // Spawned at the origin heading +x, having drawn two short segments
+        vec![("p1".to_string(), vec![
+            Segment::new(0.0, 0.0, 0.5, 0.0),
+            Segment::new(0.5, 0.0, 1.0, 0.0),
+        ])]
+    }
+
+    #[test]
+    fn test_just_spawned_bike_ignores_own_trail() {
+        let bike = PlayerState {
+            trail_armed: false,
+            ..PlayerState::new("p1".to_string(), 1.0, 0.0, 1.0, 0.0, true)
+        };
+        let results = check_all_collisions(&[bike], &spawn_stub_trail(), &CollisionConfig::default());
+        assert!(!results[0].1.collided);
+    }
+
+    #[test]
+    fn test_armed_bike_hits_own_trail() {
+        let bike = PlayerState::new("p1".to_string(), 1.0, 0.0, 1.0, 0.0, true);
+        let results = check_all_collisions(&[bike], &spawn_stub_trail(), &CollisionConfig::default());
+        assert_eq!(results[0].1.collision_type, Some(CollisionType::SelfTrail));
+    }
+
+    #[test]
+    fn test_has_cleared_spawn() {
+        assert!(!has_cleared_spawn(0.0, 0.0, 1.0, 0.0, 2.0));
+        assert!(has_cleared_spawn(0.0, 0.0, 2.0, 0.0, 2.0));
+        assert!(has_cleared_spawn(10.0, 10.0, 10.0, 14.0, 2.0));
+    }
+
+    #[test]
+    fn test_segment_normal_perpendicular_and_unit() {
+        let seg = Segment::new(0.0, 0.0, 10.0, 5.0);
+        let normal = segment_normal_at(&Vec2 { x: 3.0, z: 8.0 }, &seg);
+
+        let dot = normal.x * 10.0 + normal.z * 5.0;
+        assert!(dot.abs() < EPS);
+        assert!(((normal.x * normal.x + normal.z * normal.z).sqrt() - 1.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_segment_normal_faces_player() {
+        let seg = Segment::new(0.0, 0.0, 10.0, 0.0);
+
+        let above = segment_normal_at(&Vec2 { x: 5.0, z: 3.0 }, &seg);
+        assert!((above.z - 1.0).abs() < EPS);
+
+        let below = segment_normal_at(&Vec2 { x: 5.0, z: -3.0 }, &seg);
+        assert!((below.z + 1.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_collision_result_carries_normal() {
+        let player = PlayerState::new("p1".to_string(), 5.0, -1.0, 0.0, 1.0, true);
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+
+        let result = check_trail_collision(&player, &segments, 2.0);
+        assert!(result.collided);
+        assert_eq!(result.normal, Some(Vec2 { x: 0.0, z: -1.0 }));
+
+        let swept = continuous_collision_check(5.0, -3.0, 5.0, 3.0, &segments);
+        assert_eq!(swept.normal, Some(Vec2 { x: 0.0, z: -1.0 }));
+    }
+
+    #[test]
+    fn test_slow_perpendicular_hit_is_glance() {
+        // Driving straight into a wall, but barely moving
+        let player = PlayerState::new("p1".to_string(), 5.0, -1.0, 0.0, 1.0, true);
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+
+        let result = check_trail_collision(&player, &segments, 2.0).with_impact_speed(&player, 8.0);
+        assert!((result.impact_speed - 8.0).abs() < EPS);
+        assert_eq!(ImpactSeverity::from_impact_speed(result.impact_speed), ImpactSeverity::Glance);
+    }
+
+    #[test]
+    fn test_fast_head_on_hit_is_fatal() {
+        let player = PlayerState::new("p1".to_string(), 5.0, -1.0, 0.0, 1.0, true);
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+
+        let result = check_trail_collision(&player, &segments, 2.0).with_impact_speed(&player, 70.0);
+        assert!((result.impact_speed - 70.0).abs() < EPS);
+        assert_eq!(ImpactSeverity::from_impact_speed(result.impact_speed), ImpactSeverity::Fatal);
+    }
+
+    #[test]
+    fn test_impact_speed_scales_with_angle() {
+        let normal = Vec2 { x: 0.0, z: -1.0 };
+        // Sliding along the surface
+        assert!(impact_speed(&Vec2 { x: 1.0, z: 0.0 }, 70.0, &normal) < EPS);
+        // 60 degrees off the normal keeps half the speed
+        let dir = Vec2 { x: 3.0f32.sqrt() / 2.0, z: 0.5 };
+        assert!((impact_speed(&dir, 40.0, &normal) - 20.0).abs() < EPS);
+        assert_eq!(ImpactSeverity::from_impact_speed(20.0), ImpactSeverity::Hard);
+        assert_eq!(impact_speed(&Vec2 { x: 0.0, z: 0.0 }, 40.0, &normal), 0.0);
+    }
+
+    #[test]
+    fn test_trail_width_grows_with_speed() {
+        assert_eq!(trail_width(0.0, 0.5, 0.02), 0.5);
+        assert!((trail_width(70.0, 0.5, 0.02) - 1.9).abs() < EPS);
+        assert_eq!(trail_width(-10.0, 0.5, 0.02), 0.5);
+    }
+
+    #[test]
+    fn test_fast_segment_has_wider_collision_band() {
+        let slow = Segment::new(0.0, 0.0, 10.0, 0.0).with_width(trail_width(20.0, 0.0, 0.02));
+        let fast = Segment::new(0.0, 0.0, 10.0, 0.0).with_width(trail_width(70.0, 0.0, 0.02));
+        let player = PlayerState::new("p1".to_string(), 5.0, 2.8, 1.0, 0.0, true);
+
+        assert!(!check_trail_collision(&player, &[slow], 2.0).collided);
+        assert!(check_trail_collision(&player, &[fast], 2.0).collided);
+    }
+
+    #[test]
+    fn test_closest_collision_among_nearer_owner_wins() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let trails = vec![
+            ("p2".to_string(), vec![Segment::new(-10.0, 1.5, 10.0, 1.5)]),
+            ("p3".to_string(), vec![Segment::new(-10.0, -0.5, 10.0, -0.5)]),
+        ];
+
+        let result = closest_collision_among(&player, &trails, 2.0);
+        assert!(result.collided);
+        assert_eq!(result.collision_type, Some(CollisionType::OtherTrail("p3".to_string())));
+        assert!((result.distance - 0.5).abs() < EPS);
+    }
+
+    #[test]
+    fn test_closest_collision_among_uses_each_owners_nearest_segment() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        // p2's first segment grazes the bike, but a later one runs right by it
+        let trails = vec![
+            ("p2".to_string(), vec![
+                Segment::new(1.5, -10.0, 1.5, 10.0),
+                Segment::new(5.0, 10.0, 5.0, 20.0),
+                Segment::new(-0.2, -10.0, -0.2, 10.0),
+            ]),
+            ("p3".to_string(), vec![Segment::new(-10.0, 0.8, 10.0, 0.8)]),
+        ];
+
+        let result = closest_collision_among(&player, &trails, 2.0);
+        assert_eq!(result.collision_type, Some(CollisionType::OtherTrail("p2".to_string())));
+        assert_eq!(result.segment_index, Some(2));
+        assert!((result.distance - 0.2).abs() < EPS);
+    }
+
+    #[test]
+    fn test_closest_collision_among_order_independent() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let trails = vec![
+            ("p3".to_string(), vec![Segment::new(-10.0, -0.5, 10.0, -0.5)]),
+            ("p2".to_string(), vec![Segment::new(-10.0, 1.5, 10.0, 1.5)]),
+        ];
+
+        let result = closest_collision_among(&player, &trails, 2.0);
+        assert_eq!(result.collision_type, Some(CollisionType::OtherTrail("p3".to_string())));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_closest_collision_among_no_hit() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let trails = vec![("p2".to_string(), vec![Segment::new(-10.0, 5.0, 10.0, 5.0)])];
+
+        let result = closest_collision_among(&player, &trails, 2.0);
+        assert!(!result.collided);
+        assert!((result.distance - 5.0).abs() < EPS);
+    }
 
     #[test]
-    fn test_eps_constant() {
-        assert_eq!(EPS, 0.01);
+    fn test_pack_centroid_symmetric() {
+        let players = vec![
+            PlayerState::new("p1".to_string(), 10.0, 0.0, 1.0, 0.0, true),
+            PlayerState::new("p2".to_string(), -10.0, 0.0, 1.0, 0.0, true),
+            PlayerState::new("p3".to_string(), 0.0, 10.0, 1.0, 0.0, true),
+            PlayerState::new("p4".to_string(), 0.0, -10.0, 1.0, 0.0, true),
+        ];
+
+        let centroid = pack_centroid(&players);
+        assert!(centroid.x.abs() < EPS);
+        assert!(centroid.z.abs() < EPS);
+        assert!((pack_spread(&players, &centroid) - 10.0).abs() < EPS);
     }
 
     #[test]
-    fn test_segment_new() {
-        let seg = Segment::new(0.0, 0.0, 10.0, 10.0);
-        assert_eq!(seg.start_x, 0.0);
-        assert_eq!(seg.start_z, 0.0);
-        assert_eq!(seg.end_x, 10.0);
-        assert_eq!(seg.end_z, 10.0);
+    fn test_pack_centroid_asymmetric_ignores_dead() {
+        let players = vec![
+            PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true),
+            PlayerState::new("p2".to_string(), 30.0, 0.0, 1.0, 0.0, true),
+            PlayerState::new("p3".to_string(), 60.0, 40.0, 1.0, 0.0, true),
+            PlayerState::new("p4".to_string(), -100.0, -100.0, 1.0, 0.0, false),
+        ];
+
+        let centroid = pack_centroid(&players);
+        assert!((centroid.x - 30.0).abs() < EPS);
+        assert!((centroid.z - 40.0 / 3.0).abs() < EPS);
+        // Farthest alive bike is p3
+        let expected = (30.0f32.powi(2) + (80.0f32 / 3.0).powi(2)).sqrt();
+        assert!((pack_spread(&players, &centroid) - expected).abs() < EPS);
     }
 
     #[test]
-    fn test_segment_from_positions() {
-        let seg = Segment::from_positions(1.0, 2.0, 3.0, 4.0);
-        assert_eq!(seg.start_x, 1.0);
-        assert_eq!(seg.start_z, 2.0);
-        assert_eq!(seg.end_x, 3.0);
-        assert_eq!(seg.end_z, 4.0);
+    fn test_pack_centroid_empty() {
+        let dead = vec![PlayerState::new("p1".to_string(), 5.0, 5.0, 1.0, 0.0, false)];
+        let centroid = pack_centroid(&dead);
+        assert_eq!((centroid.x, centroid.z), (0.0, 0.0));
+        assert_eq!(pack_spread(&dead, &centroid), 0.0);
+        assert_eq!(pack_spread(&[], &centroid), 0.0);
     }
 
     #[test]
-    fn test_segment_length() {
-        let seg = Segment::new(0.0, 0.0, 3.0, 4.0);
-        assert!((seg.length() - 5.0).abs() < EPS);
+    fn test_facing_filter_ignores_segment_behind() {
+        // Heading +x with a short crossing segment just behind the bike
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let segments = vec![Segment::new(-1.0, -1.0, -1.0, 1.0)];
+
+        assert!(check_trail_collision_filtered(&player, &segments, 2.0, false).collided);
+
+        let result = check_trail_collision_filtered(&player, &segments, 2.0, true);
+        assert!(!result.collided);
+        assert!(result.segment_index.is_none());
     }
 
     #[test]
-    fn test_segment_length_zero() {
-        let seg = Segment::new(1.0, 1.0, 1.0, 1.0);
-        assert!((seg.length() - 0.0).abs() < EPS);
+    fn test_facing_filter_keeps_segment_ahead() {
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let segments = vec![Segment::new(1.0, -1.0, 1.0, 1.0)];
+
+        assert!(check_trail_collision_filtered(&player, &segments, 2.0, true).collided);
     }
 
     #[test]
-    fn test_distance_to_segment_point_on_start() {
-        let dist = distance_to_segment(0.0, 0.0, 0.0, 0.0, 10.0, 0.0);
-        assert!((dist - 0.0).abs() < EPS);
+    fn test_scratch_nearby_matches_fresh_allocation() {
+        let segments = vec![
+            Segment::new(0.0, 5.0, 10.0, 5.0),
+            Segment::new(0.0, 50.0, 10.0, 50.0),
+            Segment::new(3.0, -2.0, 3.0, 2.0),
+        ];
+        let mut scratch = CollisionScratch::default();
+
+        for (px, pz) in [(0.0, 0.0), (5.0, 48.0), (100.0, 100.0), (3.0, 0.0)] {
+            let fresh = find_segments_within_distance(px, pz, &segments, 10.0);
+            let reused = find_segments_within_distance_into(&mut scratch, px, pz, &segments, 10.0);
+            assert_eq!(reused, fresh.as_slice());
+        }
     }
 
     #[test]
-    fn test_distance_to_segment_point_on_end() {
-        let dist = distance_to_segment(10.0, 0.0, 0.0, 0.0, 10.0, 0.0);
-        assert!((dist - 0.0).abs() < EPS);
+    fn test_scratch_collisions_matches_fresh_allocation() {
+        let config = CollisionConfig::default();
+        let trails = vec![("p2".to_string(), vec![Segment::new(5.0, -5.0, 5.0, 5.0)])];
+        let mut scratch = CollisionScratch::default();
+
+        let ticks = [
+            vec![PlayerState::new("p1".to_string(), 4.0, 0.0, 1.0, 0.0, true)],
+            vec![
+                PlayerState::new("p1".to_string(), -20.0, 0.0, 1.0, 0.0, true),
+                PlayerState::new("p3".to_string(), 0.0, 0.0, 1.0, 0.0, false),
+            ],
+        ];
+        for players in &ticks {
+            let fresh = check_all_collisions(players, &trails, &config);
+            let reused = check_all_collisions_into(&mut scratch, players, &trails, &config);
+            assert_eq!(reused, fresh.as_slice());
+        }
     }
 
     #[test]
-    fn test_distance_to_segment_point_on_line() {
-        let dist = distance_to_segment(5.0, 0.0, 0.0, 0.0, 10.0, 0.0);
-        assert!((dist - 0.0).abs() < EPS);
+    fn test_prune_expired_trails() {
+        let config = CollisionConfig { trail_expiry: true, trail_lifetime: 30.0, ..Default::default() };
+        let mut trails = vec![(
+            "p1".to_string(),
+            vec![
+                Segment::new(0.0, 0.0, 10.0, 0.0).with_laid_at(5.0),
+                Segment::new(10.0, 0.0, 10.0, 10.0).with_laid_at(40.0),
+            ],
+        )];
+
+        assert_eq!(prune_expired_trails(&mut trails, 50.0, &config), 1);
+        assert_eq!(trails[0].1.len(), 1);
+        assert_eq!(trails[0].1[0].laid_at, 40.0);
     }
 
     #[test]
-    fn test_distance_to_segment_perpendicular() {
-        let dist = distance_to_segment(5.0, 3.0, 0.0, 0.0, 10.0, 0.0);
-        assert!((dist - 3.0).abs() < EPS);
+    fn test_prune_expired_trails_disabled() {
+        let config = CollisionConfig::default();
+        let mut trails = vec![("p1".to_string(), vec![Segment::new(0.0, 0.0, 10.0, 0.0)])];
+
+        assert_eq!(prune_expired_trails(&mut trails, 1_000.0, &config), 0);
+        assert_eq!(trails[0].1.len(), 1);
     }
 
     #[test]
-    fn test_distance_to_segment_beyond_start() {
-        let dist = distance_to_segment(-5.0, 0.0, 0.0, 0.0, 10.0, 0.0);
-        assert!((dist - 5.0).abs() < EPS);
+    fn test_time_to_wall_heading_at_wall() {
+        let player = PlayerState::new("p1".to_string(), 80.0, 0.0, 1.0, 0.0, true);
+        let t = time_to_wall(&player, 40.0, 100.0).unwrap();
+        assert!((t - 0.5).abs() < EPS);
     }
 
     #[test]
-    fn test_distance_to_segment_beyond_end() {
-        let dist = distance_to_segment(15.0, 0.0, 0.0, 0.0, 10.0, 0.0);
-        assert!((dist - 5.0).abs() < EPS);
+    fn test_time_to_wall_heading_away() {
+        let player = PlayerState::new("p1".to_string(), 80.0, 0.0, -1.0, 0.0, true);
+        // Crosses the whole arena to the far wall instead
+        let t = time_to_wall(&player, 40.0, 100.0).unwrap();
+        assert!((t - 4.5).abs() < EPS);
+
+        // Outside the arena and still heading out
+        let outside = PlayerState::new("p1".to_string(), 120.0, 0.0, 1.0, 0.0, true);
+        assert_eq!(time_to_wall(&outside, 40.0, 100.0), None);
     }
 
     #[test]
-    fn test_distance_to_segment_degenerate() {
-        let dist = distance_to_segment(3.0, 4.0, 0.0, 0.0, 0.0, 0.0);
-        assert!((dist - 5.0).abs() < EPS);
+    fn test_time_to_wall_diagonal_and_stationary() {
+        let dir = std::f32::consts::FRAC_1_SQRT_2;
+        let player = PlayerState::new("p1".to_string(), 0.0, 90.0, dir, dir, true);
+        // The +z wall is only 10 units away along z
+        let t = time_to_wall(&player, 10.0, 100.0).unwrap();
+        assert!((t - 10.0 / (dir * 10.0)).abs() < 1e-3);
+
+        assert_eq!(time_to_wall(&player, 0.0, 100.0), None);
     }
 
     #[test]
-    fn test_distance_to_segment_squared() {
-        let dist_sq = distance_to_segment_squared(3.0, 4.0, 0.0, 0.0, 10.0, 0.0);
-        assert!((dist_sq - 16.0).abs() < EPS);
+    fn test_wrap_position_crossing_plus_x() {
+        let (x, z) = wrap_position(101.0, 20.0, 100.0);
+        assert!((x - -99.0).abs() < EPS);
+        assert_eq!(z, 20.0);
+
+        let (x, z) = wrap_position(-100.5, -130.0, 100.0);
+        assert!((x - 99.5).abs() < EPS);
+        assert!((z - 70.0).abs() < EPS);
     }
 
     #[test]
-    fn test_distance_to_segment_struct() {
-        let seg = Segment::new(0.0, 0.0, 10.0, 0.0);
-        let dist = distance_to_segment_struct(5.0, 3.0, &seg);
-        assert!((dist - 3.0).abs() < EPS);
+    fn test_resolve_bounds_wrap_skips_death() {
+        let wall = CollisionConfig::default();
+        let wrap = CollisionConfig { bounds_mode: BoundsMode::Wrap, ..Default::default() };
+
+        assert!(resolve_bounds(105.0, 0.0, 100.0, &wall).is_err());
+        let (x, _) = resolve_bounds(105.0, 0.0, 100.0, &wrap).unwrap();
+        assert!((x - -95.0).abs() < EPS);
+
+        // Inside the arena both modes leave the position alone
+        assert_eq!(resolve_bounds(10.0, -10.0, 100.0, &wall).unwrap(), (10.0, -10.0));
+        assert_eq!(resolve_bounds(10.0, -10.0, 100.0, &wrap).unwrap(), (10.0, -10.0));
     }
 
     #[test]
-    fn test_player_state_new() {
-        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
-        assert_eq!(player.id, "p1");
-        assert_eq!(player.x, 0.0);
-        assert_eq!(player.z, 0.0);
-        assert_eq!(player.dir_x, 1.0);
-        assert_eq!(player.dir_z, 0.0);
-        assert!(player.alive);
+    fn test_crosses_seam() {
+        assert!(crosses_seam(&Segment::new(99.0, 0.0, -99.0, 0.0), 100.0));
+        assert!(!crosses_seam(&Segment::new(90.0, 0.0, 99.0, 0.0), 100.0));
     }
 
     #[test]
-    fn test_collision_result_default() {
-        let result = CollisionResult::default();
-        assert!(!result.collided);
-        assert!(result.collision_type.is_none());
-        assert_eq!(result.distance, f32::MAX);
-        assert!(result.segment_index.is_none());
+    fn test_check_all_collisions_with_rubber_leniency() {
+        let config = CollisionConfig::default();
+        let trails = vec![("p2".to_string(), vec![Segment::new(0.0, -5.0, 0.0, 5.0)])];
+        // 1.8 units from the trail: inside 2.0, outside 1.5
+        let players = vec![PlayerState::new("p1".to_string(), 1.8, 0.0, 1.0, 0.0, true)];
+
+        let none = HashMap::new();
+        let strict = check_all_collisions_with_rubber(&players, &trails, &config, &none, 0.5);
+        assert!(strict[0].1.collided);
+        assert_eq!(strict, check_all_collisions(&players, &trails, &config));
+
+        let helped: HashMap<String, f32> = [("p1".to_string(), 1.0)].into_iter().collect();
+        let lenient = check_all_collisions_with_rubber(&players, &trails, &config, &helped, 0.5);
+        assert!(!lenient[0].1.collided);
     }
 
     #[test]
-    fn test_check_trail_collision_no_collision() {
-        let player = PlayerState::new("p1".to_string(), 0.0, 10.0, 0.0, 1.0, true);
-        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
-        
-        let result = check_trail_collision(&player, &segments, 2.0);
-        assert!(!result.collided);
+    fn test_collision_type_bike() {
+        let bike = CollisionType::Bike("p2".to_string());
+        assert_eq!(format!("{:?}", bike), "Bike(\"p2\")");
+        assert_eq!(bike.to_string(), "bike p2");
+
+        let mut stats = CollisionStats::default();
+        stats.record_collision(&bike);
+        stats.record_collision(&CollisionType::Wall);
+        assert_eq!(stats.bike, 1);
+        assert_eq!(stats.total(), 2);
     }
 
     #[test]
-    fn test_check_trail_collision_hit() {
-        let player = PlayerState::new("p1".to_string(), 5.0, 0.5, 0.0, 1.0, true);
-        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
-        
-        let result = check_trail_collision(&player, &segments, 2.0);
-        assert!(result.collided);
-        assert_eq!(result.segment_index, Some(0));
+    fn test_collision_type_display_existing_variants() {
+        assert_eq!(CollisionType::SelfTrail.to_string(), "own trail");
+        assert_eq!(CollisionType::OtherTrail("p3".to_string()).to_string(), "trail of p3");
+        assert_eq!(CollisionType::Wall.to_string(), "wall");
+        // Debug output is unchanged
+        assert_eq!(format!("{:?}", CollisionType::Wall), "Wall");
     }
 
     #[test]
-    fn test_check_trail_collision_dead_player() {
-        let player = PlayerState::new("p1".to_string(), 5.0, 0.0, 0.0, 1.0, false);
-        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
-        
-        let result = check_trail_collision(&player, &segments, 2.0);
-        assert!(!result.collided);
+    fn test_ray_hits_perpendicular_segment() {
+        let wall = Segment::new(10.0, -5.0, 10.0, 5.0);
+
+        let t = ray_segment_intersection(0.0, 0.0, 1.0, 0.0, &wall).unwrap();
+        assert!((t - 10.0).abs() < EPS);
+        // Non-unit direction scales t
+        let t = ray_segment_intersection(0.0, 0.0, 2.0, 0.0, &wall).unwrap();
+        assert!((t - 5.0).abs() < EPS);
     }
 
     #[test]
-    fn test_check_trail_collision_with_owner_self() {
-        let player = PlayerState::new("p1".to_string(), 5.0, 0.5, 0.0, 1.0, true);
-        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
-        
-        let result = check_trail_collision_with_owner(&player, "p1", &segments, 2.0);
-        assert!(result.collided);
-        assert_eq!(result.collision_type, Some(CollisionType::SelfTrail));
+    fn test_ray_parallel_segment_misses() {
+        let side = Segment::new(0.0, 3.0, 20.0, 3.0);
+        assert_eq!(ray_segment_intersection(0.0, 0.0, 1.0, 0.0, &side), None);
     }
 
     #[test]
-    fn test_check_trail_collision_with_owner_other() {
-        let player = PlayerState::new("p1".to_string(), 5.0, 0.5, 0.0, 1.0, true);
-        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
-        
-        let result = check_trail_collision_with_owner(&player, "p2", &segments, 2.0);
-        assert!(result.collided);
-        assert_eq!(result.collision_type, Some(CollisionType::OtherTrail("p2".to_string())));
+    fn test_ray_ignores_segment_behind_origin() {
+        let behind = Segment::new(-10.0, -5.0, -10.0, 5.0);
+        assert_eq!(ray_segment_intersection(0.0, 0.0, 1.0, 0.0, &behind), None);
+        // Past the end of the segment is a miss too
+        let short = Segment::new(10.0, 1.0, 10.0, 5.0);
+        assert_eq!(ray_segment_intersection(0.0, 0.0, 1.0, 0.0, &short), None);
     }
 
     #[test]
-    fn test_continuous_collision_check_intersect() {
-        let segments = [Segment::new(0.0, 0.0, 10.0, 10.0)];
-        
-        // Movement crosses the segment
-        let result = continuous_collision_check(0.0, 10.0, 10.0, 0.0, &segments);
-        assert!(result.collided);
+    fn test_with_capability_sets_expected_bit() {
+        assert_eq!(with_capability(0, CAP_CONTINUOUS), 0b001);
+        assert_eq!(with_capability(0, CAP_BIKE_BIKE), 0b010);
+        assert_eq!(with_capability(CAP_CONTINUOUS, CAP_WRAP), 0b101);
+        assert!(has_capability(with_capability(0, CAP_BIKE_BIKE), CAP_BIKE_BIKE));
+        assert!(!has_capability(CAP_WRAP, CAP_BIKE_BIKE));
     }
 
     #[test]
-    fn test_continuous_collision_check_no_intersect() {
-        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
-        
-        // Movement parallel and away from segment
-        let result = continuous_collision_check(0.0, 5.0, 10.0, 5.0, &segments);
-        assert!(!result.collided);
+    fn test_collision_capabilities_round_trip() {
+        let caps = collision_capabilities();
+        let rebuilt = [CAP_CONTINUOUS, CAP_BIKE_BIKE, CAP_WRAP].iter()
+            .filter(|&&cap| has_capability(caps, cap))
+            .fold(0, |flags, &cap| with_capability(flags, cap));
+
+        assert_eq!(rebuilt, caps);
+        assert!(has_capability(caps, CAP_CONTINUOUS));
+        assert!(has_capability(caps, CAP_WRAP));
+        assert!(!has_capability(caps, CAP_BIKE_BIKE));
+    }
+
+    fn team_fixture() -> (Vec<PlayerState>, HashMap<String, u32>) {
+        let players = vec![
+            PlayerState::new("p1".to_string(), 0.5, 0.0, 1.0, 0.0, true),
+            PlayerState::new("p2".to_string(), 50.5, 0.0, 1.0, 0.0, true),
+        ];
+        let teams = [("p1", 1), ("p2", 1), ("p3", 2), ("p4", 2)]
+            .into_iter()
+            .map(|(id, team)| (id.to_string(), team))
+            .collect();
+        (players, teams)
     }
 
     #[test]
-    fn test_segments_intersect_cross() {
-        let s1 = Segment::new(0.0, 0.0, 10.0, 10.0);
-        let s2 = Segment::new(0.0, 10.0, 10.0, 0.0);
-        
-        assert!(segments_intersect(&s1, &s2));
+    fn test_friendly_fire_off_spares_teammate_trail() {
+        let (players, teams) = team_fixture();
+        let trails = vec![
+            // p1 sits on teammate p2's trail, p2 sits on enemy p3's trail
+            ("p2".to_string(), vec![Segment::new(0.0, -5.0, 0.0, 5.0)]),
+            ("p3".to_string(), vec![Segment::new(50.0, -5.0, 50.0, 5.0)]),
+        ];
+        let config = CollisionConfig::default();
+        let results = check_all_collisions_with_teams(
+            &players, &trails, &config, &teams, &TeamConfig { friendly_fire: false },
+        );
+
+        assert!(!results[0].1.collided);
+        assert!(results[1].1.collided);
+        assert_eq!(results[1].1.collision_type, Some(CollisionType::OtherTrail("p3".to_string())));
     }
 
     #[test]
-    fn test_segments_intersect_parallel() {
-        let s1 = Segment::new(0.0, 0.0, 10.0, 0.0);
-        let s2 = Segment::new(0.0, 1.0, 10.0, 1.0);
-        
-        assert!(!segments_intersect(&s1, &s2));
+    fn test_friendly_fire_on_teammate_trail_kills() {
+        let (players, teams) = team_fixture();
+        let trails = vec![
+            ("p2".to_string(), vec![Segment::new(0.0, -5.0, 0.0, 5.0)]),
+            ("p3".to_string(), vec![Segment::new(50.0, -5.0, 50.0, 5.0)]),
+        ];
+        let config = CollisionConfig::default();
+        let results = check_all_collisions_with_teams(
+            &players, &trails, &config, &teams, &TeamConfig { friendly_fire: true },
+        );
+
+        assert!(results[0].1.collided);
+        assert!(results[1].1.collided);
     }
 
     #[test]
-    fn test_segments_intersect_touch_endpoints() {
-        let s1 = Segment::new(0.0, 0.0, 5.0, 5.0);
-        let s2 = Segment::new(5.0, 5.0, 10.0, 0.0);
-        
-        assert!(segments_intersect(&s1, &s2));
+    fn test_own_trail_kills_without_friendly_fire() {
+        let (mut players, teams) = team_fixture();
+        players[0].trail_armed = true;
+        let trails = vec![(
+            "p1".to_string(),
+            vec![Segment::new(0.0, -5.0, 0.0, 5.0), Segment::new(0.0, 5.0, -10.0, 5.0)],
+        )];
+        let results = check_all_collisions_with_teams(
+            &players, &trails, &CollisionConfig::default(), &teams, &TeamConfig::default(),
+        );
+
+        assert_eq!(results[0].1.collision_type, Some(CollisionType::SelfTrail));
+    }
+
+    fn scattered_segments(count: usize, length: f32) -> Vec<Segment> {
+        (0..count)
+            .map(|i| {
+                let x = (i % 40) as f32 * 10.0 - 195.0;
+                let z = (i / 40) as f32 * 7.0 - 195.0;
+                Segment::new(x, z, x + length, z)
+            })
+            .collect()
     }
 
     #[test]
-    fn test_segments_intersect_collinear_overlap() {
-        let s1 = Segment::new(0.0, 0.0, 10.0, 0.0);
-        let s2 = Segment::new(5.0, 0.0, 15.0, 0.0);
-        
-        assert!(segments_intersect(&s1, &s2));
+    fn test_optimal_cell_size_dense_smaller_than_sparse() {
+        let sparse = optimal_cell_size(&scattered_segments(10, 5.0), 200.0);
+        let dense = optimal_cell_size(&scattered_segments(1000, 5.0), 200.0);
+
+        assert!(dense < sparse);
     }
 
     #[test]
-    fn test_check_arena_bounds_inside() {
-        let result = check_arena_bounds(50.0, 50.0, 100.0);
-        assert!(result.is_ok());
+    fn test_optimal_cell_size_within_bounds() {
+        assert_eq!(optimal_cell_size(&[], 200.0), 200.0);
+
+        for count in [1, 10, 100, 1000, 2000] {
+            let size = optimal_cell_size(&scattered_segments(count, 5.0), 200.0);
+            assert!((MIN_GRID_CELL_SIZE..=200.0).contains(&size));
+        }
+        // Never finer than the average segment
+        assert!(optimal_cell_size(&scattered_segments(2000, 30.0), 200.0) >= 30.0);
+        // Tiny arenas still get a usable cell
+        assert_eq!(optimal_cell_size(&scattered_segments(10, 1.0), 1.0), MIN_GRID_CELL_SIZE);
     }
 
     #[test]
-    fn test_check_arena_bounds_outside() {
-        let result = check_arena_bounds(150.0, 50.0, 100.0);
-        assert!(result.is_err());
+    fn test_immune_pairing_does_not_collide() {
+        let trails = vec![
+            ("p2".to_string(), vec![Segment::new(0.0, -5.0, 0.0, 5.0)]),
+            ("p3".to_string(), vec![Segment::new(50.0, -5.0, 50.0, 5.0)]),
+        ];
+        let players = vec![
+            // Tutorial bike immune to p2 sits on p2's trail
+            PlayerState::new("p1".to_string(), 0.5, 0.0, 1.0, 0.0, true)
+                .with_immunity(vec!["p2".to_string()]),
+            // Same immunity doesn't help against p3
+            PlayerState::new("p4".to_string(), 50.5, 0.0, 1.0, 0.0, true)
+                .with_immunity(vec!["p2".to_string()]),
+            // No immunity at all
+            PlayerState::new("p5".to_string(), 0.5, 1.0, 1.0, 0.0, true),
+        ];
+        let results = check_all_collisions(&players, &trails, &CollisionConfig::default());
+
+        assert!(!results[0].1.collided);
+        assert_eq!(results[1].1.collision_type, Some(CollisionType::OtherTrail("p3".to_string())));
+        assert_eq!(results[2].1.collision_type, Some(CollisionType::OtherTrail("p2".to_string())));
     }
 
     #[test]
-    fn test_check_arena_bounds_edge() {
-        let result = check_arena_bounds(99.0, 50.0, 100.0);
-        assert!(result.is_ok());
+    fn test_which_wall_each_side() {
+        assert_eq!(which_wall(0.0, 0.0, 100.0), None);
+        assert_eq!(which_wall(0.0, 150.0, 100.0), Some(WallSide::North));
+        assert_eq!(which_wall(0.0, -150.0, 100.0), Some(WallSide::South));
+        assert_eq!(which_wall(150.0, 0.0, 100.0), Some(WallSide::East));
+        assert_eq!(which_wall(-150.0, 0.0, 100.0), Some(WallSide::West));
     }
 
     #[test]
-    fn test_check_wall_collision_safe() {
-        assert!(!check_wall_collision(50.0, 50.0, 100.0, 5.0));
+    fn test_which_wall_corner() {
+        // Deeper overshoot wins
+        assert_eq!(which_wall(110.0, 130.0, 100.0), Some(WallSide::North));
+        assert_eq!(which_wall(-130.0, -110.0, 100.0), Some(WallSide::West));
+        // Exact diagonal falls to the x axis
+        assert_eq!(which_wall(-120.0, 120.0, 100.0), Some(WallSide::West));
     }
 
     #[test]
-    fn test_check_wall_collision_hit() {
-        assert!(check_wall_collision(98.0, 50.0, 100.0, 5.0));
+    fn test_which_wall_matches_bounds_check() {
+        for &(x, z) in &[(0.0, 0.0), (99.0, 0.0), (101.0, 0.0), (0.0, -101.0), (150.0, 150.0)] {
+            assert_eq!(which_wall(x, z, 100.0).is_some(), check_arena_bounds(x, z, 100.0).is_err());
+        }
     }
 
     #[test]
-    fn test_check_slipstream_behind() {
-        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 0.0, 1.0, true);
-        let leader = PlayerState::new("p2".to_string(), 0.0, 3.0, 0.0, 1.0, true);
-        
-        assert!(check_slipstream(&player, &leader, 5.0, 0.3));
+    fn test_wall_normals_point_inward() {
+        for (side, (x, z)) in [
+            (WallSide::North, (0.0, 150.0)),
+            (WallSide::South, (0.0, -150.0)),
+            (WallSide::East, (150.0, 0.0)),
+            (WallSide::West, (-150.0, 0.0)),
+        ] {
+            let (nx, nz) = side.inward_normal();
+            assert!(nx * x + nz * z < 0.0);
+        }
     }
 
     #[test]
-    fn test_check_slipstream_too_far() {
-        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 0.0, 1.0, true);
-        let leader = PlayerState::new("p2".to_string(), 0.0, 10.0, 0.0, 1.0, true);
-        
-        assert!(!check_slipstream(&player, &leader, 5.0, 0.3));
+    fn test_empty_owner_is_not_other_trail() {
+        let player = PlayerState::new("p1".to_string(), 5.0, 0.5, 1.0, 0.0, true);
+        let segments = [Segment::new(0.0, 0.0, 10.0, 0.0)];
+
+        let result = check_trail_collision_with_owner(&player, "", &segments, 2.0);
+        assert!(result.collided);
+        assert_eq!(result.collision_type, Some(CollisionType::UnknownTrail));
+
+        // A bike with an empty id doesn't own an empty-owner trail either
+        let anonymous = PlayerState::new(String::new(), 5.0, 0.5, 1.0, 0.0, true);
+        let result = check_trail_collision_with_owner(&anonymous, "", &segments, 2.0);
+        assert_eq!(result.collision_type, Some(CollisionType::UnknownTrail));
     }
 
     #[test]
-    fn test_check_slipstream_wrong_angle() {
-        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
-        let leader = PlayerState::new("p2".to_string(), 0.0, 3.0, 0.0, 1.0, true);
-        
-        assert!(!check_slipstream(&player, &leader, 5.0, 0.3));
+    fn test_continuous_check_reports_unknown_trail() {
+        let segments = [Segment::new(0.0, -5.0, 0.0, 5.0)];
+
+        let swept = continuous_collision_check(-3.0, 0.0, 3.0, 0.0, &segments);
+        assert_eq!(swept.collision_type, Some(CollisionType::UnknownTrail));
+
+        let stationary = continuous_collision_check(0.5, 0.0, 0.5, 0.0, &segments);
+        assert_eq!(stationary.collision_type, Some(CollisionType::UnknownTrail));
     }
 
     #[test]
-    fn test_find_closest_segment() {
-        let segments = [
-            Segment::new(0.0, 0.0, 10.0, 0.0),
-            Segment::new(0.0, 10.0, 10.0, 10.0),
-        ];
-        
-        let result = find_closest_segment(5.0, 1.0, &segments);
-        assert_eq!(result.unwrap().0, 0);
+    fn test_trail_collision_type_attribution() {
+        assert_eq!(trail_collision_type("p1", "p1"), CollisionType::SelfTrail);
+        assert_eq!(trail_collision_type("p1", "p2"), CollisionType::OtherTrail("p2".to_string()));
+        assert_eq!(trail_collision_type("p1", ""), CollisionType::UnknownTrail);
+        assert_eq!(trail_collision_type("p1", HAZARD_OWNER), CollisionType::Hazard);
+        assert_eq!(CollisionType::UnknownTrail.to_string(), "unknown trail");
+    }
+
+    /// Ten far-off trails plus a near one that the bike is sitting on
+    fn crowded_trails() -> Vec<(String, Vec<Segment>)> {
+        let mut trails: Vec<(String, Vec<Segment>)> = (0..10)
+            .map(|i| {
+                let x = -150.0 + i as f32 * 10.0;
+                (format!("far{}", i), vec![Segment::new(x, 100.0, x, 150.0)])
+            })
+            .collect();
+        trails.push(("near".to_string(), vec![Segment::new(0.0, -10.0, 0.0, 10.0)]));
+        trails
     }
 
     #[test]
-    fn test_find_closest_segment_empty() {
-        let segments: [Segment; 0] = [];
-        assert!(find_closest_segment(0.0, 0.0, &segments).is_none());
+    fn test_nearest_trail_owners_picks_nearby_trails() {
+        let player = PlayerState::new("p1".to_string(), 1.0, 0.0, 1.0, 0.0, true);
+        let trails = crowded_trails();
+
+        let owners = nearest_trail_owners(&player, &trails, 2, 0.0);
+        assert_eq!(owners.len(), 2);
+        assert_eq!(owners[0], "near");
+        assert!(!owners.contains(&"far0"));
+
+        // Empty trails are never candidates
+        let empty = vec![("ghost".to_string(), Vec::new())];
+        assert!(nearest_trail_owners(&player, &empty, 3, 2.0).is_empty());
     }
 
     #[test]
-    fn test_find_segments_within_distance() {
-        let segments = [
-            Segment::new(0.0, 0.0, 10.0, 0.0),
-            Segment::new(0.0, 10.0, 10.0, 10.0),
-        ];
-        
-        let results = find_segments_within_distance(5.0, 1.0, &segments, 3.0);
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].0, 0);
+    fn test_nearest_trails_checks_every_box_around_the_bike() {
+        // Four L-shaped trails whose boxes all contain the bike without any
+        // segment near it, listed before the trail it is actually on
+        let mut trails: Vec<(String, Vec<Segment>)> = (0..4)
+            .map(|i| {
+                let r = 50.0 + i as f32 * 10.0;
+                (format!("box{}", i), vec![Segment::new(-r, -r, r, -r), Segment::new(r, -r, r, r)])
+            })
+            .collect();
+        trails.push(("hit".to_string(), vec![Segment::new(0.0, -10.0, 0.0, 10.0)]));
+        let player = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+
+        let owners = nearest_trail_owners(&player, &trails, 2, 2.0);
+        assert!(owners.contains(&"hit"));
+
+        let hit = closest_collision_among_nearest(&player, &trails, 2.0, 2);
+        assert_eq!(hit.collision_type, Some(CollisionType::OtherTrail("hit".to_string())));
     }
 
     #[test]
-    fn test_collision_config_constants() {
-        assert_eq!(COLLISION_CONFIG.death_radius, 2.0);
-        assert_eq!(COLLISION_CONFIG.bike_collision_dist, 3.0);
-        assert_eq!(COLLISION_CONFIG.trail_collision_dist, 2.5);
+    fn test_nearest_trails_still_detects_real_collision() {
+        let player = PlayerState::new("p1".to_string(), 1.0, 0.0, 1.0, 0.0, true);
+        let trails = crowded_trails();
+
+        let hit = closest_collision_among_nearest(&player, &trails, 2.0, 1);
+        assert!(hit.collided);
+        assert_eq!(hit.collision_type, Some(CollisionType::OtherTrail("near".to_string())));
+        assert_eq!(hit, closest_collision_among(&player, &trails, 2.0));
     }
 
     #[test]
-    fn test_collision_type_debug() {
-        let self_trail = CollisionType::SelfTrail;
-        assert_eq!(format!("{:?}", self_trail), "SelfTrail");
-        
-        let other = CollisionType::OtherTrail("p2".to_string());
-        assert!(format!("{:?}", other).contains("p2"));
-        
-        let wall = CollisionType::Wall;
-        assert_eq!(format!("{:?}", wall), "Wall");
+    fn test_check_all_collisions_uses_nearest_trails() {
+        let players = [PlayerState::new("p1".to_string(), 1.0, 0.0, 1.0, 0.0, true)];
+        let trails = crowded_trails();
+        let config = CollisionConfig { nearest_trails: 3, ..COLLISION_CONFIG };
+
+        let results = check_all_collisions(&players, &trails, &config);
+        assert!(results[0].1.collided);
+    }
+
+    #[test]
+    fn test_immunity_never_covers_own_trail() {
+        let bike = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true)
+            .with_immunity(vec!["p1".to_string()]);
+        assert!(!bike.is_immune_to("p1"));
     }
 }
@@ -5,7 +5,7 @@
 //! - Collision detection thresholds
 //! - Rubber banding settings
 
-use crate::physics::PhysicsError;
+use crate::physics::{PhysicsError, EPS};
 
 /// Physics configuration for bike movement
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -27,9 +27,21 @@ pub struct PhysicsConfig {
     /// Deceleration rate (units per second squared)
     pub deceleration: f32,
     /// Minimum speed before stopping
+    ///
+    /// Invariant: while racing, speeds produced by `get_target_speed`,
+    /// `approach_speed` and `apply_turn_penalty` never drop below this, so
+    /// no braking setup can make a bike reverse along its trail.
     pub min_speed: f32,
     /// Maximum speed cap
     pub max_speed: f32,
+    /// Negate turn angles for clients whose steering convention is reversed
+    pub invert_steering: bool,
+    /// Tightest turning circle allowed (units, 0 disables the limit)
+    pub min_turn_radius: f32,
+    /// Largest heading change in a single tick (radians, 0 disables the cap)
+    pub max_turn_per_tick: f32,
+    /// Shape of speed changes in `approach_speed_curved`
+    pub accel_curve: AccelCurve,
 }
 
 impl Default for PhysicsConfig {
@@ -45,6 +57,10 @@ impl Default for PhysicsConfig {
             deceleration: 80.0,
             min_speed: 5.0,
             max_speed: 80.0,
+            invert_steering: false,
+            min_turn_radius: 2.0,
+            max_turn_per_tick: 0.0,
+            accel_curve: AccelCurve::Linear,
         }
     }
 }
@@ -132,9 +148,63 @@ impl PhysicsConfig {
             ));
         }
         
+        if self.boost_speed > self.max_speed {
+            return Err(PhysicsError::InvalidConfig(
+                "boost_speed cannot exceed max_speed".to_string()
+            ));
+        }
+        
+        if self.min_turn_radius < 0.0 {
+            return Err(PhysicsError::InvalidConfig(
+                "min_turn_radius cannot be negative".to_string()
+            ));
+        }
+        
+        if self.max_turn_per_tick < 0.0 {
+            return Err(PhysicsError::InvalidConfig(
+                "max_turn_per_tick cannot be negative".to_string()
+            ));
+        }
+        
         Ok(())
     }
 
+    /// Look for settings that are legal but probably not what a designer wants
+    ///
+    /// Unlike `validate`, nothing here is fatal; the config still works, it
+    /// just plays oddly.
+    ///
+    /// # Returns
+    /// One human-readable warning per suspicious combination (empty if none)
+    pub fn lint(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        let boost_ramp = (self.boost_speed - self.base_speed) / self.acceleration;
+        if boost_ramp > LINT_MAX_BOOST_RAMP {
+            warnings.push(format!(
+                "acceleration {} takes {:.1}s to reach boost_speed from base_speed (over {}s)",
+                self.acceleration, boost_ramp, LINT_MAX_BOOST_RAMP
+            ));
+        }
+
+        let turning_speed = self.base_speed * (1.0 - self.turn_penalty);
+        if turning_speed < self.brake_speed {
+            warnings.push(format!(
+                "turn_penalty {} drops a turning bike to {:.1}, slower than brake_speed {}",
+                self.turn_penalty, turning_speed, self.brake_speed
+            ));
+        }
+
+        if self.min_turn_radius > 0.0 && self.base_speed / self.min_turn_radius < self.turn_speed {
+            warnings.push(format!(
+                "min_turn_radius {} caps turning below turn_speed {} at base_speed",
+                self.min_turn_radius, self.turn_speed
+            ));
+        }
+
+        warnings
+    }
+
     /// Get the speed for current input state
     ///
     /// # Arguments
@@ -142,36 +212,128 @@ impl PhysicsConfig {
     /// * `is_braking` - Whether player is braking
     ///
     /// # Returns
-    /// Target speed value
+    /// Target speed value, never below `min_speed`
     pub fn get_target_speed(&self, is_boosting: bool, is_braking: bool) -> f32 {
-        if is_boosting {
+        let target = if is_boosting {
             self.boost_speed
         } else if is_braking {
             self.brake_speed
         } else {
             self.base_speed
+        };
+
+        target.max(self.min_speed)
+    }
+
+    /// Move the current speed toward a target speed
+    ///
+    /// Speeds up at `acceleration` and slows down at `deceleration`
+    /// without overshooting the target.
+    ///
+    /// # Arguments
+    /// * `current` - Current speed
+    /// * `target` - Desired speed (see `get_target_speed`)
+    /// * `dt` - Delta time in seconds
+    ///
+    /// # Returns
+    /// New speed, never below `min_speed`
+    pub fn approach_speed(&self, current: f32, target: f32, dt: f32) -> f32 {
+        let target = target.max(self.min_speed);
+        let next = if current < target {
+            (current + self.acceleration * dt).min(target)
+        } else {
+            (current - self.deceleration * dt).max(target)
+        };
+
+        next.max(self.min_speed)
+    }
+
+    /// Move the current speed toward a target speed along `accel_curve`
+    ///
+    /// `Linear` is exactly `approach_speed`. `EaseOut` closes the gap
+    /// exponentially with time constant `EASE_OUT_TIME`, snapping once
+    /// within `EPS`. Neither overshoots the target.
+    ///
+    /// # Arguments
+    /// * `current` - Current speed
+    /// * `target` - Desired speed (see `get_target_speed`)
+    /// * `dt` - Delta time in seconds
+    ///
+    /// # Returns
+    /// New speed, never below `min_speed`
+    pub fn approach_speed_curved(&self, current: f32, target: f32, dt: f32) -> f32 {
+        match self.accel_curve {
+            AccelCurve::Linear => self.approach_speed(current, target, dt),
+            AccelCurve::EaseOut => {
+                let target = target.max(self.min_speed);
+                let gap = (target - current) * (-dt / EASE_OUT_TIME).exp();
+                let next = if gap.abs() < EPS { target } else { target - gap };
+                next.max(self.min_speed)
+            }
         }
     }
 
     /// Calculate turn angle for a given delta time
     ///
+    /// When `max_turn_per_tick` is set, the result never exceeds it in
+    /// magnitude, however large `dt` is. The default leaves it unset.
+    ///
     /// # Arguments
     /// * `dt` - Delta time in seconds
     /// * `turning_left` - Whether turning left
     /// * `turning_right` - Whether turning right
     ///
     /// # Returns
-    /// Angle to turn in radians (positive = left, negative = right)
+    /// Angle to turn in radians (positive = left, negative = right,
+    /// or the reverse when `invert_steering` is set)
     pub fn calculate_turn_angle(&self, dt: f32, turning_left: bool, turning_right: bool) -> f32 {
-        if turning_left && !turning_right {
+        let angle = if turning_left && !turning_right {
             self.turn_speed * dt
         } else if turning_right && !turning_left {
             -self.turn_speed * dt
         } else {
             0.0
+        };
+
+        // A dt spike must not turn into an instant spin
+        let angle = if self.max_turn_per_tick > 0.0 {
+            angle.clamp(-self.max_turn_per_tick, self.max_turn_per_tick)
+        } else {
+            angle
+        };
+
+        if self.invert_steering {
+            -angle
+        } else {
+            angle
         }
     }
 
+    /// Calculate turn angle for a given delta time at the current speed
+    ///
+    /// Like `calculate_turn_angle`, but the turn rate is capped by
+    /// `max_turn_rate_for_speed` so slow bikes can't pivot in place.
+    ///
+    /// # Arguments
+    /// * `dt` - Delta time in seconds
+    /// * `speed` - Current speed (units per second)
+    /// * `turning_left` - Whether turning left
+    /// * `turning_right` - Whether turning right
+    ///
+    /// # Returns
+    /// Angle to turn in radians (same sign convention as `calculate_turn_angle`)
+    pub fn calculate_turn_angle_at_speed(
+        &self,
+        dt: f32,
+        speed: f32,
+        turning_left: bool,
+        turning_right: bool,
+    ) -> f32 {
+        let rate = max_turn_rate_for_speed(self.turn_speed, speed, self.min_turn_radius);
+        let limited = PhysicsConfig { turn_speed: rate, ..*self };
+        limited.calculate_turn_angle(dt, turning_left, turning_right)
+    }
+
     /// Apply turn penalty to speed
     ///
     /// # Arguments
@@ -179,16 +341,65 @@ impl PhysicsConfig {
     /// * `is_turning` - Whether currently turning
     ///
     /// # Returns
-    /// Speed after penalty application
+    /// Speed after penalty application; the penalty alone never takes a
+    /// bike below `min_speed`
     pub fn apply_turn_penalty(&self, current_speed: f32, is_turning: bool) -> f32 {
         if is_turning {
-            current_speed * (1.0 - self.turn_penalty)
+            let floor = self.min_speed.min(current_speed);
+            (current_speed * (1.0 - self.turn_penalty)).max(floor)
         } else {
             current_speed
         }
     }
 }
 
+/// Caps angular velocity so the turning radius stays above a minimum
+///
+/// A bike moving at `speed` and turning at `rate` traces a circle of radius
+/// `speed / rate`, so the rate is limited to `speed / min_radius`.
+///
+/// # Arguments
+/// * `turn_speed` - Configured turn rate (radians per second)
+/// * `speed` - Current speed (units per second)
+/// * `min_radius` - Minimum turning radius (units, 0 disables the limit)
+///
+/// # Returns
+/// Allowed turn rate in radians per second
+pub fn max_turn_rate_for_speed(turn_speed: f32, speed: f32, min_radius: f32) -> f32 {
+    if min_radius <= 0.0 {
+        return turn_speed;
+    }
+
+    turn_speed.min(speed.max(0.0) / min_radius)
+}
+
+/// Longest `PhysicsConfig::lint` accepts for ramping from base to boost speed (seconds)
+pub const LINT_MAX_BOOST_RAMP: f32 = 3.0;
+
+/// Time constant of the `AccelCurve::EaseOut` ramp (seconds)
+pub const EASE_OUT_TIME: f32 = 0.25;
+
+/// How speed ramps toward its target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AccelCurve {
+    /// Constant `acceleration`/`deceleration`
+    #[default]
+    Linear,
+    /// Exponential approach: fast while far from the target, settling
+    /// smoothly as it gets close
+    EaseOut,
+}
+
+/// What happens when a bike reaches the arena edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoundsMode {
+    /// The edge is a wall; crossing it is a crash
+    #[default]
+    Wall,
+    /// The arena is a torus; crossing an edge wraps to the opposite one
+    Wrap,
+}
+
 /// Collision detection configuration
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct CollisionConfig {
@@ -204,6 +415,17 @@ pub struct CollisionConfig {
     pub slipstream_distance: f32,
     /// Maximum angle for slipstream effect (radians, cos value)
     pub slipstream_angle: f32,
+    /// Floating-point tolerance for collision math (units)
+    pub eps: f32,
+    /// Whether trail segments expire after `trail_lifetime`
+    pub trail_expiry: bool,
+    /// Seconds a trail segment lasts when `trail_expiry` is on
+    pub trail_lifetime: f32,
+    /// Wall or wrap-around arena edges
+    pub bounds_mode: BoundsMode,
+    /// Nearest owners whose trails get a full collision check beyond those
+    /// close enough to collide (0 = all)
+    pub nearest_trails: usize,
 }
 
 impl Default for CollisionConfig {
@@ -215,6 +437,11 @@ impl Default for CollisionConfig {
             wall_collision_dist: 1.0,
             slipstream_distance: 5.0,
             slipstream_angle: 0.3,
+            eps: 0.01,
+            trail_expiry: false,
+            trail_lifetime: 60.0,
+            bounds_mode: BoundsMode::Wall,
+            nearest_trails: 8,
         }
     }
 }
@@ -271,6 +498,18 @@ impl CollisionConfig {
             ));
         }
         
+        if self.eps <= 0.0 {
+            return Err(PhysicsError::InvalidConfig(
+                "eps must be positive".to_string()
+            ));
+        }
+        
+        if self.trail_lifetime <= 0.0 {
+            return Err(PhysicsError::InvalidConfig(
+                "trail_lifetime must be positive".to_string()
+            ));
+        }
+        
         Ok(())
     }
 
@@ -283,6 +522,18 @@ impl CollisionConfig {
     pub fn trail_collision_dist_squared(&self) -> f32 {
         self.trail_collision_dist * self.trail_collision_dist
     }
+
+    /// Get squared slipstream distance
+    pub fn slipstream_distance_squared(&self) -> f32 {
+        self.slipstream_distance * self.slipstream_distance
+    }
+}
+
+/// Team mode configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TeamConfig {
+    /// Whether teammates' trails kill (own trail always does)
+    pub friendly_fire: bool,
 }
 
 /// Rubber banding configuration
@@ -308,6 +559,8 @@ pub struct RubberConfig {
     pub min_rubber: f32,
     /// Threshold for effectiveness calculation
     pub effectiveness_threshold: f32,
+    /// Effectiveness at which catch-up tiers 1, 2 and 3 begin (ascending)
+    pub catchup_thresholds: [f32; 3],
 }
 
 impl Default for RubberConfig {
@@ -323,6 +576,7 @@ impl Default for RubberConfig {
             max_rubber: 5.0,
             min_rubber: 0.1,
             effectiveness_threshold: 0.5,
+            catchup_thresholds: [0.5, 0.7, 0.9],
         }
     }
 }
@@ -404,6 +658,13 @@ impl RubberConfig {
             ));
         }
         
+        let [low, mid, high] = self.catchup_thresholds;
+        if low < 0.0 || high > 1.0 || low > mid || mid > high {
+            return Err(PhysicsError::InvalidConfig(
+                "catchup_thresholds must be ascending and between 0.0 and 1.0".to_string()
+            ));
+        }
+        
         Ok(())
     }
 
@@ -430,6 +691,65 @@ impl RubberConfig {
     }
 }
 
+/// Boost energy configuration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoostConfig {
+    /// Energy of a full boost bar
+    pub max_energy: f32,
+    /// Energy spent per second of boosting
+    pub drain_rate: f32,
+    /// Energy regained per second while not boosting
+    pub regen_rate: f32,
+    /// Seconds boosting stays locked after the bar is emptied
+    pub boost_cooldown: f32,
+}
+
+impl Default for BoostConfig {
+    fn default() -> Self {
+        Self {
+            max_energy: 100.0,
+            drain_rate: 40.0,
+            regen_rate: 15.0,
+            boost_cooldown: 1.5,
+        }
+    }
+}
+
+impl BoostConfig {
+    /// Validate the boost configuration
+    ///
+    /// # Returns
+    /// * `Ok(())` if configuration is valid
+    /// * `Err` with details if invalid
+    pub fn validate(&self) -> Result<(), PhysicsError> {
+        if self.max_energy <= 0.0 {
+            return Err(PhysicsError::InvalidConfig(
+                "max_energy must be positive".to_string()
+            ));
+        }
+
+        if self.drain_rate <= 0.0 {
+            return Err(PhysicsError::InvalidConfig(
+                "drain_rate must be positive".to_string()
+            ));
+        }
+
+        if self.regen_rate < 0.0 {
+            return Err(PhysicsError::InvalidConfig(
+                "regen_rate cannot be negative".to_string()
+            ));
+        }
+
+        if self.boost_cooldown < 0.0 {
+            return Err(PhysicsError::InvalidConfig(
+                "boost_cooldown cannot be negative".to_string()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// Complete physics configuration bundle
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct FullPhysicsConfig {
@@ -475,6 +795,10 @@ impl FullPhysicsConfig {
                 deceleration: 80.0,
                 min_speed: 5.0,
                 max_speed: 80.0,
+                invert_steering: false,
+                min_turn_radius: 2.0,
+                max_turn_per_tick: 0.5,
+                accel_curve: AccelCurve::Linear,
             },
             collision: CollisionConfig {
                 death_radius: 2.0,
@@ -483,6 +807,11 @@ impl FullPhysicsConfig {
                 wall_collision_dist: 1.0,
                 slipstream_distance: 5.0,
                 slipstream_angle: 0.3,
+                eps: 0.01,
+                trail_expiry: false,
+                trail_lifetime: 60.0,
+                bounds_mode: BoundsMode::Wall,
+                nearest_trails: 8,
             },
             rubber: RubberConfig {
                 base_rubber: 1.0,
@@ -495,6 +824,7 @@ impl FullPhysicsConfig {
                 max_rubber: 5.0,
                 min_rubber: 0.1,
                 effectiveness_threshold: 0.5,
+                catchup_thresholds: [0.5, 0.7, 0.9],
             },
         }
     }
@@ -513,6 +843,10 @@ impl FullPhysicsConfig {
                 deceleration: 60.0,
                 min_speed: 5.0,
                 max_speed: 70.0,
+                invert_steering: false,
+                min_turn_radius: 2.0,
+                max_turn_per_tick: 0.5,
+                accel_curve: AccelCurve::Linear,
             },
             collision: CollisionConfig {
                 death_radius: 2.5,
@@ -521,6 +855,11 @@ impl FullPhysicsConfig {
                 wall_collision_dist: 1.5,
                 slipstream_distance: 6.0,
                 slipstream_angle: 0.4,
+                eps: 0.01,
+                trail_expiry: false,
+                trail_lifetime: 60.0,
+                bounds_mode: BoundsMode::Wall,
+                nearest_trails: 8,
             },
             rubber: RubberConfig {
                 base_rubber: 1.0,
@@ -533,11 +872,27 @@ impl FullPhysicsConfig {
                 max_rubber: 6.0,
                 min_rubber: 0.1,
                 effectiveness_threshold: 0.4,
+                catchup_thresholds: [0.4, 0.6, 0.8],
             },
         }
     }
 }
 
+/// Looks up a named physics preset
+///
+/// # Arguments
+/// * `name` - Preset name ("competitive" or "casual")
+///
+/// # Returns
+/// The preset, or None for an unknown name
+pub fn preset_by_name(name: &str) -> Option<FullPhysicsConfig> {
+    match name {
+        "competitive" => Some(FullPhysicsConfig::competitive()),
+        "casual" => Some(FullPhysicsConfig::casual()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -582,6 +937,18 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_physics_config_validate_boost_above_max() {
+        let config = PhysicsConfig { boost_speed: 90.0, ..Default::default() };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_physics_config_validate_boost_at_max() {
+        let config = PhysicsConfig { boost_speed: 80.0, ..Default::default() };
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_physics_config_validate_brake_greater_than_base() {
         let config = PhysicsConfig { brake_speed: 50.0, ..Default::default() };
@@ -608,6 +975,30 @@ mod tests {
         assert!(config_high.validate().is_err());
     }
 
+    #[test]
+    fn test_physics_config_lint_defaults_clean() {
+        assert!(PhysicsConfig::default().lint().is_empty());
+        assert!(FullPhysicsConfig::competitive().physics.lint().is_empty());
+        assert!(FullPhysicsConfig::casual().physics.lint().is_empty());
+    }
+
+    #[test]
+    fn test_physics_config_lint_pathological() {
+        let config = PhysicsConfig {
+            acceleration: 5.0,
+            turn_penalty: 0.6,
+            min_turn_radius: 20.0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let warnings = config.lint();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("acceleration"));
+        assert!(warnings[1].contains("turn_penalty"));
+        assert!(warnings[2].contains("min_turn_radius"));
+    }
+
     #[test]
     fn test_physics_config_get_target_speed() {
         let config = PhysicsConfig::default();
@@ -636,6 +1027,73 @@ mod tests {
         assert_eq!(both, 0.0);
     }
 
+    #[test]
+    fn test_turn_angle_capped_on_dt_spike() {
+        let config = FullPhysicsConfig::competitive().physics;
+
+        // A full second at 3 rad/s would be a 172 degree spin
+        assert_eq!(config.calculate_turn_angle(1.0, true, false), config.max_turn_per_tick);
+        assert_eq!(config.calculate_turn_angle(1.0, false, true), -config.max_turn_per_tick);
+    }
+
+    #[test]
+    fn test_default_turn_angle_uncapped() {
+        let config = PhysicsConfig::default();
+        assert_eq!(config.max_turn_per_tick, 0.0);
+        assert!((config.calculate_turn_angle(1.0, true, false) - 3.0).abs() < 0.01);
+        assert!((config.calculate_turn_angle(1.0, false, true) + 3.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_normal_turn_not_capped() {
+        let config = PhysicsConfig::default();
+        let dt = 1.0 / 60.0;
+
+        assert!((config.calculate_turn_angle(dt, true, false) - config.turn_speed * dt).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_max_turn_rate_throttled_at_low_speed() {
+        // 5 u/s with a 2 unit radius allows at most 2.5 rad/s
+        assert!((max_turn_rate_for_speed(3.0, 5.0, 2.0) - 2.5).abs() < 0.001);
+        assert_eq!(max_turn_rate_for_speed(3.0, 0.0, 2.0), 0.0);
+    }
+
+    #[test]
+    fn test_max_turn_rate_unaffected_at_high_speed() {
+        assert_eq!(max_turn_rate_for_speed(3.0, 40.0, 2.0), 3.0);
+        assert_eq!(max_turn_rate_for_speed(3.0, 5.0, 0.0), 3.0);
+    }
+
+    #[test]
+    fn test_physics_config_turn_angle_at_speed() {
+        let config = PhysicsConfig::default();
+        let dt = 0.1;
+
+        let fast = config.calculate_turn_angle_at_speed(dt, 40.0, true, false);
+        assert_eq!(fast, config.calculate_turn_angle(dt, true, false));
+
+        let slow = config.calculate_turn_angle_at_speed(dt, 5.0, false, true);
+        assert!((slow - (-0.25)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_physics_config_invert_steering() {
+        let normal = PhysicsConfig::default();
+        let inverted = PhysicsConfig { invert_steering: true, ..Default::default() };
+        let dt = 0.1;
+
+        let left = inverted.calculate_turn_angle(dt, true, false);
+        assert_eq!(left, -normal.calculate_turn_angle(dt, true, false));
+        assert!(left < 0.0);
+
+        let right = inverted.calculate_turn_angle(dt, false, true);
+        assert_eq!(right, -normal.calculate_turn_angle(dt, false, true));
+        assert!(right > 0.0);
+
+        assert_eq!(inverted.calculate_turn_angle(dt, false, false), 0.0);
+    }
+
     #[test]
     fn test_physics_config_apply_turn_penalty() {
         let config = PhysicsConfig::default();
@@ -647,6 +1105,33 @@ mod tests {
         assert!((with_penalty - 38.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_physics_config_approach_speed() {
+        let config = PhysicsConfig::default();
+
+        // 100 u/s^2 for 0.1s, capped at the target
+        assert!((config.approach_speed(40.0, 70.0, 0.1) - 50.0).abs() < 0.01);
+        assert_eq!(config.approach_speed(65.0, 70.0, 0.1), 70.0);
+        // 80 u/s^2 for 0.1s down toward brake speed
+        assert!((config.approach_speed(40.0, 20.0, 0.1) - 32.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_extreme_braking_clamps_to_min_speed() {
+        let config = PhysicsConfig {
+            brake_speed: -50.0,
+            deceleration: 10_000.0,
+            turn_penalty: 1.0,
+            ..Default::default()
+        };
+
+        let target = config.get_target_speed(false, true);
+        assert_eq!(target, config.min_speed);
+        assert_eq!(config.approach_speed(40.0, target, 1.0), config.min_speed);
+        assert_eq!(config.approach_speed(40.0, -1000.0, 1.0), config.min_speed);
+        assert_eq!(config.apply_turn_penalty(40.0, true), config.min_speed);
+    }
+
     // ========================================================================
     // CollisionConfig Tests
     // ========================================================================
@@ -685,12 +1170,27 @@ mod tests {
         assert!(config_high.validate().is_err());
     }
 
+    #[test]
+    fn test_collision_config_validate_eps() {
+        assert_eq!(CollisionConfig::default().eps, 0.01);
+        let config = CollisionConfig { eps: 0.0, ..Default::default() };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_collision_config_validate_trail_lifetime() {
+        assert!(!CollisionConfig::default().trail_expiry);
+        let config = CollisionConfig { trail_lifetime: 0.0, ..Default::default() };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_collision_config_squared_values() {
         let config = CollisionConfig::default();
         
         assert_eq!(config.death_radius_squared(), 4.0);
         assert_eq!(config.trail_collision_dist_squared(), 6.25);
+        assert_eq!(config.slipstream_distance_squared(), 25.0);
     }
 
     // ========================================================================
@@ -725,6 +1225,14 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_rubber_config_validate_catchup_thresholds() {
+        let unordered = RubberConfig { catchup_thresholds: [0.7, 0.5, 0.9], ..Default::default() };
+        let out_of_range = RubberConfig { catchup_thresholds: [0.5, 0.7, 1.2], ..Default::default() };
+        assert!(unordered.validate().is_err());
+        assert!(out_of_range.validate().is_err());
+    }
+
     #[test]
     fn test_rubber_config_validate_decay_rate_invalid() {
         let config_low = RubberConfig { decay_rate: 0.0, ..Default::default() };
@@ -796,4 +1304,53 @@ mod tests {
         config.physics.base_speed = 0.0;
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_preset_by_name() {
+        assert_eq!(preset_by_name("competitive"), Some(FullPhysicsConfig::competitive()));
+        assert_eq!(preset_by_name("casual"), Some(FullPhysicsConfig::casual()));
+        assert_eq!(preset_by_name("turbo"), None);
+    }
+
+    #[test]
+    fn test_boost_config_validate() {
+        assert!(BoostConfig::default().validate().is_ok());
+        assert!(BoostConfig { boost_cooldown: -1.0, ..Default::default() }.validate().is_err());
+        assert!(BoostConfig { drain_rate: 0.0, ..Default::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_approach_speed_curved_default_is_linear() {
+        let config = PhysicsConfig::default();
+        assert_eq!(config.accel_curve, AccelCurve::Linear);
+        assert_eq!(config.approach_speed_curved(5.0, 40.0, 0.1), config.approach_speed(5.0, 40.0, 0.1));
+    }
+
+    #[test]
+    fn test_ease_out_starts_faster_than_linear() {
+        let linear = PhysicsConfig::default();
+        let eased = PhysicsConfig { accel_curve: AccelCurve::EaseOut, ..linear };
+
+        let l = linear.approach_speed_curved(5.0, 40.0, 0.1);
+        let e = eased.approach_speed_curved(5.0, 40.0, 0.1);
+        assert!(e > l);
+    }
+
+    #[test]
+    fn test_both_curves_converge_without_overshoot() {
+        for curve in [AccelCurve::Linear, AccelCurve::EaseOut] {
+            let config = PhysicsConfig { accel_curve: curve, ..Default::default() };
+            for (start, target) in [(5.0, 70.0), (70.0, 20.0)] {
+                let mut speed = start;
+                for _ in 0..300 {
+                    let next = config.approach_speed_curved(speed, target, 1.0 / 60.0);
+                    // Monotonic toward the target, never past it
+                    assert!((target - next).abs() <= (target - speed).abs());
+                    assert!((next - target) * (start - target) >= 0.0);
+                    speed = next;
+                }
+                assert_eq!(speed, target, "{:?} from {} to {}", curve, start, target);
+            }
+        }
+    }
 }
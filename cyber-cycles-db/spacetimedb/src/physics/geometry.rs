@@ -0,0 +1,104 @@
+//! Vector geometry helpers for Cyber Cycles
+//!
+//! This module centralizes direction math shared by several systems:
+//! - Head-on detection
+//! - Slipstream cones
+//! - Turn-point detection
+
+use crate::Vec2;
+
+/// Calculates the unsigned angle between two direction vectors
+///
+/// Uses `atan2` of the cross and dot products, so inputs do not need to
+/// be normalized and the result stays accurate near 0 and PI.
+///
+/// # Arguments
+/// * `a` - First direction
+/// * `b` - Second direction
+///
+/// # Returns
+/// Angle in radians (0.0 to PI)
+pub fn angle_between(a: &Vec2, b: &Vec2) -> f32 {
+    let cross = a.x * b.z - a.z * b.x;
+    let dot = a.x * b.x + a.z * b.z;
+    cross.abs().atan2(dot)
+}
+
+/// Calculates the signed angle that turns one direction onto another
+///
+/// Positive (counter-clockwise, +X towards +Z) is a left turn, matching
+/// `PhysicsConfig::calculate_turn_angle`. Exactly opposite directions give
+/// +PI rather than -PI.
+///
+/// # Arguments
+/// * `from` - Current direction
+/// * `to` - Goal direction
+///
+/// # Returns
+/// Angle in radians (-PI exclusive to PI inclusive)
+pub fn signed_angle(from: &Vec2, to: &Vec2) -> f32 {
+    let cross = from.x * to.z - from.z * to.x;
+    let dot = from.x * to.x + from.z * to.z;
+    let angle = cross.atan2(dot);
+    if angle <= -std::f32::consts::PI {
+        std::f32::consts::PI
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::collision::EPS;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn test_angle_between_parallel() {
+        let a = Vec2 { x: 1.0, z: 0.0 };
+        let b = Vec2 { x: 5.0, z: 0.0 };
+        assert!(angle_between(&a, &b).abs() < EPS);
+    }
+
+    #[test]
+    fn test_angle_between_perpendicular() {
+        let a = Vec2 { x: 1.0, z: 0.0 };
+        let b = Vec2 { x: 0.0, z: 3.0 };
+        assert!((angle_between(&a, &b) - FRAC_PI_2).abs() < EPS);
+        assert!((angle_between(&b, &a) - FRAC_PI_2).abs() < EPS);
+    }
+
+    #[test]
+    fn test_angle_between_opposite() {
+        let a = Vec2 { x: 0.0, z: 2.0 };
+        let b = Vec2 { x: 0.0, z: -0.5 };
+        assert!((angle_between(&a, &b) - PI).abs() < EPS);
+    }
+
+    #[test]
+    fn test_signed_angle_left_is_positive() {
+        let x = Vec2 { x: 1.0, z: 0.0 };
+        let z = Vec2 { x: 0.0, z: 4.0 };
+        assert!((signed_angle(&x, &z) - FRAC_PI_2).abs() < EPS);
+    }
+
+    #[test]
+    fn test_signed_angle_right_is_negative() {
+        let x = Vec2 { x: 1.0, z: 0.0 };
+        let neg_z = Vec2 { x: 0.0, z: -1.0 };
+        assert!((signed_angle(&x, &neg_z) + FRAC_PI_2).abs() < EPS);
+        assert!((signed_angle(&neg_z, &x) - FRAC_PI_2).abs() < EPS);
+    }
+
+    #[test]
+    fn test_signed_angle_range() {
+        let x = Vec2 { x: 1.0, z: 0.0 };
+        let neg_x = Vec2 { x: -1.0, z: 0.0 };
+        let behind_right = Vec2 { x: -1.0, z: -0.01 };
+
+        assert_eq!(signed_angle(&x, &neg_x), PI);
+        assert!(signed_angle(&x, &behind_right) > -PI);
+        assert!(signed_angle(&x, &behind_right) < -PI + 0.1);
+        assert!(signed_angle(&x, &x).abs() < EPS);
+    }
+}
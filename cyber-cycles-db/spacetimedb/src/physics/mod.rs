@@ -2,17 +2,25 @@
 //!
 //! This module provides server-authoritative physics calculations including:
 //! - Rubber banding system for catch-up mechanics
+//! - Boost energy with a post-depletion cooldown
 //! - Collision detection with trails and arena bounds
 //! - Configuration for physics parameters
+//! - Shared vector geometry helpers
 
 pub mod rubber;
+pub mod boost;
 pub mod collision;
 pub mod config;
+pub mod geometry;
 
 // Re-export commonly used types
-pub use rubber::{RubberState, RUBBER_CONFIG};
-pub use collision::{EPS, CollisionType};
-pub use config::{PhysicsConfig, CollisionConfig, RubberConfig};
+pub use rubber::{MalusSource, RubberState, RUBBER_CONFIG};
+pub use boost::BoostState;
+pub use collision::{EPS, CollisionType, ImpactSeverity, IntersectionKind, WallSide};
+pub use config::{AccelCurve, BoostConfig, BoundsMode, PhysicsConfig, CollisionConfig, RubberConfig, TeamConfig};
+
+use collision::{PlayerState, Segment};
+use config::FullPhysicsConfig;
 
 /// Physics validation result type
 pub type PhysicsResult<T> = Result<T, PhysicsError>;
@@ -50,6 +58,9 @@ impl std::fmt::Display for PhysicsError {
                 write!(f, "Rubber mismatch: client={}, server={}, tolerance={}", 
                        client_value, server_value, tolerance)
             }
+            PhysicsError::Collision { player_id, collision_type: CollisionType::Bike(other_id) } => {
+                write!(f, "Collision detected for {}: bike {}", player_id, other_id)
+            }
             PhysicsError::Collision { player_id, collision_type } => {
                 write!(f, "Collision detected for {}: {:?}", player_id, collision_type)
             }
@@ -76,6 +87,150 @@ pub fn validate_physics_state(
     Ok(())
 }
 
+/// Validates a client-reported speed against the physics config
+///
+/// A stopped bike (speed 0) is always valid; otherwise the speed must lie in
+/// `[min_speed, max_speed * (1 + tolerance)]`. The tolerance absorbs
+/// network jitter on top of the boost cap.
+///
+/// # Arguments
+/// * `speed` - Reported speed (units/sec)
+/// * `config` - Physics configuration
+/// * `tolerance` - Fraction of `max_speed` allowed above the cap
+///
+/// # Returns
+/// * `Ok(())` if the speed is plausible
+/// * `Err(PhysicsError::InvalidState)` otherwise
+pub fn validate_speed(speed: f32, config: &PhysicsConfig, tolerance: f32) -> PhysicsResult<()> {
+    let max_allowed = config.max_speed * (1.0 + tolerance);
+
+    if speed == 0.0 || (speed >= config.min_speed && speed <= max_allowed) {
+        Ok(())
+    } else {
+        Err(PhysicsError::InvalidState(format!(
+            "speed {} outside [{}, {}]", speed, config.min_speed, max_allowed
+        )))
+    }
+}
+
+/// Clamps a speed into `[min_speed, max_speed]`
+///
+/// NaN clamps to `min_speed`.
+pub fn clamp_speed(speed: f32, config: &PhysicsConfig) -> f32 {
+    speed.max(config.min_speed).min(config.max_speed)
+}
+
+/// Seconds a round has spent in sudden-death overtime
+///
+/// # Arguments
+/// * `round_secs` - Seconds since the round went live
+/// * `overtime_after` - Round time at which overtime begins (0 or less = never)
+///
+/// # Returns
+/// Seconds past the overtime threshold, or 0.0 before it
+pub fn overtime_secs(round_secs: f32, overtime_after: f32) -> f32 {
+    if overtime_after <= 0.0 {
+        return 0.0;
+    }
+    (round_secs - overtime_after).max(0.0)
+}
+
+/// Escalates a speed the longer sudden-death overtime runs
+///
+/// Grows linearly so a stalled round is forced to resolve.
+///
+/// # Arguments
+/// * `base` - Speed outside overtime (units/sec)
+/// * `overtime_secs` - Seconds spent in overtime (see `overtime_secs`)
+/// * `rate` - Fraction of `base` gained per second of overtime
+///
+/// # Returns
+/// Escalated speed, or `base` unchanged before overtime
+pub fn overtime_speed_scale(base: f32, overtime_secs: f32, rate: f32) -> f32 {
+    if overtime_secs <= 0.0 {
+        return base;
+    }
+    base * (1.0 + rate.max(0.0) * overtime_secs)
+}
+
+/// Controls and timing for one physics step of a bike
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepInput {
+    /// Delta time in seconds
+    pub dt: f32,
+    /// Speed at the start of the step (units/sec)
+    pub speed: f32,
+    /// Turn-left button held
+    pub turning_left: bool,
+    /// Turn-right button held
+    pub turning_right: bool,
+    /// Brake button held
+    pub braking: bool,
+    /// Boost button held
+    pub boosting: bool,
+    /// Half-size of the arena
+    pub arena_size: f32,
+}
+
+/// Speed a bike ends a step with
+///
+/// Approaches the target speed for the held buttons along the configured
+/// acceleration curve, then applies the turn penalty while turning.
+pub fn step_speed(input: &StepInput, config: &PhysicsConfig) -> f32 {
+    let target = config.get_target_speed(input.boosting, input.braking);
+    let speed = config.approach_speed_curved(input.speed, target, input.dt);
+    config.apply_turn_penalty(speed, input.turning_left != input.turning_right)
+}
+
+/// Advances one bike by a single physics step
+///
+/// Runs turn, speed, move, bounds and collision in that order, so the whole
+/// per-player update can be tested without the database. Dead bikes are
+/// returned unchanged. The speed the bike ends on is `step_speed`.
+///
+/// # Arguments
+/// * `player` - Bike state at the start of the step
+/// * `input` - Controls, speed and timing for this step
+/// * `config` - Physics and collision configuration
+/// * `trails` - Trail segments keyed by owner id
+///
+/// # Returns
+/// * `Ok(PlayerState)` - the advanced bike
+/// * `Err(PhysicsError::OutOfBounds)` if it hit an arena wall
+/// * `Err(PhysicsError::Collision)` if it hit a trail
+pub fn step_player(
+    player: &PlayerState,
+    input: &StepInput,
+    config: &FullPhysicsConfig,
+    trails: &[(String, Vec<Segment>)],
+) -> PhysicsResult<PlayerState> {
+    if !player.alive {
+        return Ok(player.clone());
+    }
+
+    let angle = config.physics.calculate_turn_angle_at_speed(
+        input.dt, input.speed, input.turning_left, input.turning_right,
+    );
+    let (sin, cos) = angle.sin_cos();
+    let dir_x = player.dir_x * cos - player.dir_z * sin;
+    let dir_z = player.dir_x * sin + player.dir_z * cos;
+
+    let speed = step_speed(input, &config.physics);
+    let x = player.x + dir_x * speed * input.dt;
+    let z = player.z + dir_z * speed * input.dt;
+    let (x, z) = collision::resolve_bounds(x, z, input.arena_size, &config.collision)?;
+
+    let next = PlayerState { x, z, dir_x, dir_z, ..player.clone() };
+    let hit = collision::closest_collision_among(&next, trails, config.collision.death_radius);
+    match hit.collision_type {
+        Some(collision_type) if hit.collided => Err(PhysicsError::Collision {
+            player_id: next.id,
+            collision_type,
+        }),
+        _ => Ok(next),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,6 +251,21 @@ mod tests {
         assert_eq!(format!("{:?}", collision), "SelfTrail");
     }
 
+    #[test]
+    fn test_physics_error_collision_display() {
+        let bike = PhysicsError::Collision {
+            player_id: "p1".to_string(),
+            collision_type: CollisionType::Bike("p2".to_string()),
+        };
+        assert_eq!(bike.to_string(), "Collision detected for p1: bike p2");
+
+        let wall = PhysicsError::Collision {
+            player_id: "p1".to_string(),
+            collision_type: CollisionType::Wall,
+        };
+        assert_eq!(wall.to_string(), "Collision detected for p1: Wall");
+    }
+
     #[test]
     fn test_validate_physics_state_valid() {
         let result = validate_physics_state("p1", 50.0, 50.0, 200.0);
@@ -107,4 +277,107 @@ mod tests {
         let result = validate_physics_state("p1", 250.0, 250.0, 200.0);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_validate_speed_legal() {
+        let config = PhysicsConfig::default();
+        assert!(validate_speed(40.0, &config, 0.1).is_ok());
+        assert!(validate_speed(0.0, &config, 0.1).is_ok());
+        // Within tolerance above max_speed
+        assert!(validate_speed(85.0, &config, 0.1).is_ok());
+    }
+
+    #[test]
+    fn test_validate_speed_over_boost_clamped() {
+        let config = PhysicsConfig::default();
+        assert!(validate_speed(90.0, &config, 0.1).is_err());
+        assert_eq!(clamp_speed(90.0, &config), config.max_speed);
+    }
+
+    fn step_input() -> StepInput {
+        StepInput {
+            dt: 0.1,
+            speed: 40.0,
+            turning_left: false,
+            turning_right: false,
+            braking: false,
+            boosting: false,
+            arena_size: 200.0,
+        }
+    }
+
+    #[test]
+    fn test_step_player_clean_step_advances() {
+        let bike = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let next = step_player(&bike, &step_input(), &FullPhysicsConfig::default(), &[]).unwrap();
+
+        assert!((next.x - 4.0).abs() < 1e-4);
+        assert_eq!(next.z, 0.0);
+        assert_eq!((next.dir_x, next.dir_z), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_step_player_turns_before_moving() {
+        let bike = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let input = StepInput { turning_left: true, ..step_input() };
+        let next = step_player(&bike, &input, &FullPhysicsConfig::default(), &[]).unwrap();
+
+        // Left is counter-clockwise: heading and position both gain +z
+        assert!(next.dir_z > 0.0);
+        assert!(next.z > 0.0);
+        assert!(step_speed(&input, &PhysicsConfig::default()) < 40.0);
+    }
+
+    #[test]
+    fn test_step_player_wall_hit() {
+        let bike = PlayerState::new("p1".to_string(), 198.5, 0.0, 1.0, 0.0, true);
+        let result = step_player(&bike, &step_input(), &FullPhysicsConfig::default(), &[]);
+        assert!(matches!(result, Err(PhysicsError::OutOfBounds { .. })));
+    }
+
+    #[test]
+    fn test_step_player_trail_hit() {
+        let bike = PlayerState::new("p1".to_string(), 0.0, 0.0, 1.0, 0.0, true);
+        let trails = vec![("p2".to_string(), vec![Segment::new(4.0, -10.0, 4.0, 10.0)])];
+        let result = step_player(&bike, &step_input(), &FullPhysicsConfig::default(), &trails);
+
+        assert_eq!(result, Err(PhysicsError::Collision {
+            player_id: "p1".to_string(),
+            collision_type: CollisionType::OtherTrail("p2".to_string()),
+        }));
+    }
+
+    #[test]
+    fn test_step_player_dead_bike_unchanged() {
+        let bike = PlayerState::new("p1".to_string(), 198.5, 0.0, 1.0, 0.0, false);
+        assert_eq!(step_player(&bike, &step_input(), &FullPhysicsConfig::default(), &[]), Ok(bike));
+    }
+
+    #[test]
+    fn test_validate_speed_absurd_rejected() {
+        let config = PhysicsConfig::default();
+        let result = validate_speed(10_000.0, &config, 0.1);
+        assert!(matches!(result, Err(PhysicsError::InvalidState(_))));
+        assert!(validate_speed(-5.0, &config, 0.1).is_err());
+        assert!(validate_speed(f32::NAN, &config, 0.1).is_err());
+        assert_eq!(clamp_speed(f32::NAN, &config), config.min_speed);
+    }
+
+    #[test]
+    fn test_overtime_speed_climbs_with_duration() {
+        let short = overtime_speed_scale(40.0, 5.0, 0.02);
+        let long = overtime_speed_scale(40.0, 20.0, 0.02);
+
+        assert!(short > 40.0);
+        assert!(long > short);
+        assert!((long - 56.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_overtime_speed_unchanged_before_overtime() {
+        assert_eq!(overtime_secs(59.0, 60.0), 0.0);
+        assert_eq!(overtime_secs(500.0, 0.0), 0.0);
+        assert_eq!(overtime_secs(75.0, 60.0), 15.0);
+        assert_eq!(overtime_speed_scale(40.0, overtime_secs(59.0, 60.0), 0.02), 40.0);
+    }
 }
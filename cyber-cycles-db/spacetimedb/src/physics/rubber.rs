@@ -3,6 +3,8 @@
 //! The rubber banding system provides catch-up mechanics by tracking
 //! player performance and applying dynamic adjustments.
 
+use std::collections::HashMap;
+
 use crate::physics::config::RubberConfig;
 use crate::physics::collision::EPS;
 
@@ -18,8 +20,24 @@ pub const RUBBER_CONFIG: RubberConfig = RubberConfig {
     max_rubber: 5.0,
     min_rubber: 0.1,
     effectiveness_threshold: 0.5,
+    catchup_thresholds: [0.5, 0.7, 0.9],
 };
 
+/// Lobby size at which the rubber ceiling reaches its full value
+pub const FULL_RUBBER_PLAYERS: u32 = 8;
+
+/// Fraction of the rubber ceiling left in a two-player duel
+pub const DUEL_RUBBER_FRACTION: f32 = 0.5;
+
+/// What caused a player's malus, for telemetry and the HUD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MalusSource {
+    /// Penalty for turning
+    Turn,
+    /// Penalty for grazing or hitting something
+    Collision,
+}
+
 /// State of the rubber banding system for a player
 #[derive(Debug, Clone, PartialEq)]
 pub struct RubberState {
@@ -31,6 +49,8 @@ pub struct RubberState {
     pub malus: f32,
     /// Timer for malus duration (seconds)
     pub malus_timer: f32,
+    /// Cause of the most recent malus, if any
+    pub last_malus_source: Option<MalusSource>,
 }
 
 impl Default for RubberState {
@@ -40,6 +60,7 @@ impl Default for RubberState {
             rubber: RUBBER_CONFIG.base_rubber,
             malus: 0.0,
             malus_timer: 0.0,
+            last_malus_source: None,
         }
     }
 }
@@ -52,6 +73,7 @@ impl RubberState {
             rubber: RUBBER_CONFIG.base_rubber,
             malus: 0.0,
             malus_timer: 0.0,
+            last_malus_source: None,
         }
     }
 
@@ -62,6 +84,7 @@ impl RubberState {
             rubber: rubber.clamp(RUBBER_CONFIG.min_rubber, RUBBER_CONFIG.max_rubber),
             malus: 0.0,
             malus_timer: 0.0,
+            last_malus_source: None,
         }
     }
 }
@@ -76,11 +99,36 @@ impl RubberState {
 /// # Returns
 /// The updated rubber value after decay
 pub fn update_rubber(state: &mut RubberState, dt: f32, config: Option<&RubberConfig>) -> f32 {
+    update_rubber_active(state, dt, true, config)
+}
+
+/// Updates the rubber state, decaying only while the player is active
+///
+/// Decay only runs while `active` (alive and racing), so rubber is not
+/// drained before a round starts or after a bike dies. The malus timer
+/// keeps counting down either way.
+///
+/// # Arguments
+/// * `state` - Mutable reference to the rubber state
+/// * `dt` - Delta time in seconds
+/// * `active` - Whether the player is alive in a racing round
+/// * `config` - Rubber configuration (uses RUBBER_CONFIG if None)
+///
+/// # Returns
+/// The updated rubber value after decay
+pub fn update_rubber_active(
+    state: &mut RubberState,
+    dt: f32,
+    active: bool,
+    config: Option<&RubberConfig>,
+) -> f32 {
     let cfg = config.unwrap_or(&RUBBER_CONFIG);
     
     // Apply exponential decay to rubber
-    let decay_factor = cfg.decay_rate.powf(dt);
-    state.rubber *= decay_factor;
+    if active {
+        let decay_factor = cfg.decay_rate.powf(dt);
+        state.rubber *= decay_factor;
+    }
     
     // Clamp to valid range
     state.rubber = state.rubber.clamp(cfg.min_rubber, cfg.max_rubber);
@@ -97,6 +145,24 @@ pub fn update_rubber(state: &mut RubberState, dt: f32, config: Option<&RubberCon
     state.rubber
 }
 
+/// Rubber ceiling for a lobby of the given size
+///
+/// Strong catch-up feels unfair in a duel but is fine in a big pack, so
+/// the ceiling rises linearly from `DUEL_RUBBER_FRACTION` of `base_max`
+/// at two players to the full `base_max` at `FULL_RUBBER_PLAYERS`.
+///
+/// # Arguments
+/// * `base_max` - Rubber ceiling for a full lobby
+/// * `player_count` - Players taking part in the round
+///
+/// # Returns
+/// Scaled rubber ceiling
+pub fn scaled_max_rubber(base_max: f32, player_count: u32) -> f32 {
+    let span = (FULL_RUBBER_PLAYERS - 2) as f32;
+    let t = (player_count.clamp(2, FULL_RUBBER_PLAYERS) - 2) as f32 / span;
+    base_max * (DUEL_RUBBER_FRACTION + (1.0 - DUEL_RUBBER_FRACTION) * t)
+}
+
 /// Applies a malus (penalty) to the player after a turn
 ///
 /// # Arguments
@@ -107,6 +173,23 @@ pub fn update_rubber(state: &mut RubberState, dt: f32, config: Option<&RubberCon
 /// # Returns
 /// The applied malus value
 pub fn apply_malus(state: &mut RubberState, duration: f32, factor: f32) -> f32 {
+    apply_malus_from(state, duration, factor, MalusSource::Turn)
+}
+
+/// Applies a malus (penalty) to the player, recording what caused it
+///
+/// The source only labels the malus; the penalty itself is the same as
+/// `apply_malus` for every source.
+///
+/// # Arguments
+/// * `state` - Mutable reference to the rubber state
+/// * `duration` - Duration of the malus in seconds
+/// * `factor` - Malus factor (0.0 to 1.0, higher = more penalty)
+/// * `source` - What caused the malus, stored as `last_malus_source`
+///
+/// # Returns
+/// The applied malus value
+pub fn apply_malus_from(state: &mut RubberState, duration: f32, factor: f32, source: MalusSource) -> f32 {
     let cfg = &RUBBER_CONFIG;
     
     // Clamp factor to valid range
@@ -115,6 +198,7 @@ pub fn apply_malus(state: &mut RubberState, duration: f32, factor: f32) -> f32 {
     // Calculate malus based on current rubber and factor
     state.malus = state.rubber * clamped_factor * cfg.malus_factor;
     state.malus_timer = duration.max(cfg.malus_duration);
+    state.last_malus_source = Some(source);
     
     state.malus
 }
@@ -147,6 +231,108 @@ pub fn calculate_effectiveness(state: &RubberState) -> f32 {
     (rubber_effectiveness - malus_reduction).clamp(0.0, 1.0)
 }
 
+/// Checks whether the rubber band is helping a player at all
+///
+/// Catch-up aids (speed boost, collision leniency) only kick in once
+/// effectiveness reaches `effectiveness_threshold`.
+///
+/// # Arguments
+/// * `state` - Reference to the rubber state
+///
+/// # Returns
+/// True if effectiveness is at or above the threshold
+pub fn rubber_is_active(state: &RubberState) -> bool {
+    calculate_effectiveness(state) >= RUBBER_CONFIG.effectiveness_threshold
+}
+
+/// Maps rubber effectiveness to a catch-up tier for the HUD
+///
+/// Tier 0 means no catch-up; tiers 1 to 3 start at each of
+/// `RUBBER_CONFIG.catchup_thresholds` in turn.
+///
+/// # Arguments
+/// * `effectiveness` - Rubber effectiveness (0.0 to 1.0, see `calculate_effectiveness`)
+///
+/// # Returns
+/// Catch-up tier (0 to 3)
+pub fn catchup_tier(effectiveness: f32) -> u8 {
+    catchup_tier_with(effectiveness, &RUBBER_CONFIG.catchup_thresholds)
+}
+
+/// Maps rubber effectiveness to a catch-up tier with custom thresholds
+///
+/// # Arguments
+/// * `effectiveness` - Rubber effectiveness (0.0 to 1.0)
+/// * `thresholds` - Effectiveness at which tiers 1, 2 and 3 begin (ascending)
+///
+/// # Returns
+/// Catch-up tier (0 to 3)
+pub fn catchup_tier_with(effectiveness: f32, thresholds: &[f32; 3]) -> u8 {
+    thresholds.iter().filter(|&&t| effectiveness >= t).count() as u8
+}
+
+/// Maps rubber effectiveness and race position to a catch-up tier
+///
+/// The leader's effectiveness counts half; each place further back
+/// counts more, up to the full value for last place. Without a race
+/// (fewer than two players, or an out-of-range position) the tier comes
+/// from effectiveness alone.
+///
+/// # Arguments
+/// * `effectiveness` - Rubber effectiveness (0.0 to 1.0)
+/// * `position` - Current race position (1 = first place)
+/// * `total_players` - Number of players in the race
+/// * `thresholds` - Effectiveness at which tiers 1, 2 and 3 begin (ascending)
+///
+/// # Returns
+/// Catch-up tier (0 to 3)
+pub fn catchup_tier_for_position(
+    effectiveness: f32,
+    position: u32,
+    total_players: u32,
+    thresholds: &[f32; 3],
+) -> u8 {
+    if total_players < 2 || position == 0 || position > total_players {
+        return catchup_tier_with(effectiveness, thresholds);
+    }
+
+    let position_factor = (position - 1) as f32 / (total_players - 1) as f32;
+    catchup_tier_with(effectiveness * (0.5 + 0.5 * position_factor), thresholds)
+}
+
+/// Effectiveness of every player the rubber band is actively helping
+///
+/// Inactive players are left out, so `check_all_collisions_with_rubber`
+/// gives them the normal death radius.
+///
+/// # Arguments
+/// * `states` - Rubber states of all players
+///
+/// # Returns
+/// Map of player id to effectiveness
+pub fn active_effectiveness(states: &[RubberState]) -> HashMap<String, f32> {
+    states.iter()
+        .filter(|s| rubber_is_active(s))
+        .map(|s| (s.player_id.clone(), calculate_effectiveness(s)))
+        .collect()
+}
+
+/// Shrinks the death radius for players the rubber band is helping
+///
+/// A small comeback aid: trailing players get a slightly more forgiving
+/// hitbox, scaled by their rubber effectiveness.
+///
+/// # Arguments
+/// * `base` - Normal death radius (units)
+/// * `effectiveness` - Rubber effectiveness (0.0 to 1.0, see `calculate_effectiveness`)
+/// * `max_reduction` - Radius removed at full effectiveness (units)
+///
+/// # Returns
+/// Reduced death radius, never negative
+pub fn death_radius_with_rubber(base: f32, effectiveness: f32, max_reduction: f32) -> f32 {
+    (base - max_reduction * effectiveness.clamp(0.0, 1.0)).max(0.0)
+}
+
 /// Validates rubber usage for anti-cheat purposes
 ///
 /// # Arguments
@@ -188,8 +374,12 @@ pub fn validate_rubber_usage(
 pub fn calculate_speed_modifier(state: &RubberState, base_speed: f32) -> f32 {
     let cfg = &RUBBER_CONFIG;
     
-    // Rubber provides a speed boost
-    let rubber_boost = (state.rubber - cfg.base_rubber) * cfg.rubber_speed * 0.01;
+    // Rubber provides a speed boost, but only once it is active
+    let rubber_boost = if rubber_is_active(state) {
+        (state.rubber - cfg.base_rubber) * cfg.rubber_speed * 0.01
+    } else {
+        0.0
+    };
     
     // Malus reduces speed
     let malus_penalty = state.malus;
@@ -219,6 +409,7 @@ pub fn reset_rubber(state: &mut RubberState) {
     state.rubber = RUBBER_CONFIG.base_rubber;
     state.malus = 0.0;
     state.malus_timer = 0.0;
+    state.last_malus_source = None;
 }
 
 /// Increases rubber based on player performance (being behind)
@@ -254,10 +445,66 @@ pub fn increase_rubber_for_position(
     state.rubber
 }
 
+/// Smoothly accrues rubber based on race position
+///
+/// Unlike `increase_rubber_for_position`, the bonus is a rate per second
+/// scaled by `dt`, so rubber builds up gradually, independent of frame
+/// rate, and without jumps when positions change. The rate comes from
+/// `RubberConfig::calculate_position_bonus`, which counts ranks from the
+/// back of the pack, so last place accrues fastest and first place none.
+///
+/// # Arguments
+/// * `state` - Mutable reference to the rubber state
+/// * `position` - Current race position (1 = first place)
+/// * `total_players` - Total number of players
+/// * `dt` - Delta time in seconds
+/// * `config` - Rubber configuration (uses RUBBER_CONFIG if None)
+///
+/// # Returns
+/// New rubber value
+pub fn accrue_rubber_for_position(
+    state: &mut RubberState,
+    position: u32,
+    total_players: u32,
+    dt: f32,
+    config: Option<&RubberConfig>,
+) -> f32 {
+    let cfg = config.unwrap_or(&RUBBER_CONFIG);
+
+    if total_players == 0 || position == 0 || position > total_players || dt <= 0.0 {
+        return state.rubber;
+    }
+
+    let rank_from_back = total_players - position + 1;
+    let rate = cfg.calculate_position_bonus(rank_from_back, total_players);
+
+    state.rubber = (state.rubber + rate * dt).clamp(cfg.min_rubber, cfg.max_rubber);
+    state.rubber
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_duel_has_lower_rubber_cap_than_pack() {
+        let duel = scaled_max_rubber(RUBBER_CONFIG.max_rubber, 2);
+        let pack = scaled_max_rubber(RUBBER_CONFIG.max_rubber, 8);
+        assert!(duel < pack);
+        assert_eq!(pack, RUBBER_CONFIG.max_rubber);
+        // The duel cap still leaves room above a fresh bike's rubber
+        assert!(duel > RUBBER_CONFIG.base_rubber);
+    }
+
+    #[test]
+    fn test_scaled_max_rubber_monotonic() {
+        let caps: Vec<f32> = (0..=12).map(|n| scaled_max_rubber(5.0, n)).collect();
+        assert!(caps.windows(2).all(|w| w[0] <= w[1]));
+        // Flat outside the scaled range
+        assert_eq!(caps[0], caps[2]);
+        assert_eq!(caps[8], caps[12]);
+    }
+
     #[test]
     fn test_rubber_state_default() {
         let state = RubberState::default();
@@ -574,4 +821,161 @@ mod tests {
         let new_rubber = update_rubber(&mut state, 1.0, Some(&custom_config));
         assert!(new_rubber < 2.0);
     }
+
+    #[test]
+    fn test_accrue_rubber_last_place_climbs_smoothly() {
+        let mut state = RubberState::new("p6");
+        let mut previous = state.rubber;
+
+        for _ in 0..10 {
+            let rubber = accrue_rubber_for_position(&mut state, 6, 6, 0.1, None);
+            let step = rubber - previous;
+            assert!(step > 0.0 && step < 0.01);
+            previous = rubber;
+        }
+    }
+
+    #[test]
+    fn test_accrue_rubber_first_place_unchanged() {
+        let mut state = RubberState::new("p1");
+        for _ in 0..10 {
+            accrue_rubber_for_position(&mut state, 1, 6, 0.1, None);
+        }
+        assert_eq!(state.rubber, RUBBER_CONFIG.base_rubber);
+    }
+
+    #[test]
+    fn test_accrue_rubber_frame_rate_independent() {
+        let mut coarse = RubberState::new("p1");
+        let mut fine = RubberState::new("p1");
+
+        accrue_rubber_for_position(&mut coarse, 6, 6, 1.0, None);
+        for _ in 0..10 {
+            accrue_rubber_for_position(&mut fine, 6, 6, 0.1, None);
+        }
+        assert!((coarse.rubber - fine.rubber).abs() < EPS);
+    }
+
+    #[test]
+    fn test_rubber_preserved_through_countdown() {
+        let mut state = RubberState::with_rubber("p1", 0.8);
+
+        // Three seconds of countdown ticks
+        for _ in 0..30 {
+            update_rubber_active(&mut state, 0.1, false, None);
+        }
+        assert_eq!(state.rubber, 0.8);
+    }
+
+    #[test]
+    fn test_rubber_decays_once_racing() {
+        let mut state = RubberState::with_rubber("p1", 0.8);
+
+        update_rubber_active(&mut state, 1.0, false, None);
+        assert_eq!(state.rubber, 0.8);
+
+        update_rubber(&mut state, 1.0, None);
+        assert!(state.rubber < 0.8);
+    }
+
+    #[test]
+    fn test_malus_source_turn() {
+        let mut state = RubberState::with_rubber("p1", 2.0);
+        assert_eq!(state.last_malus_source, None);
+
+        apply_malus_from(&mut state, 0.5, 0.5, MalusSource::Turn);
+        assert_eq!(state.last_malus_source, Some(MalusSource::Turn));
+    }
+
+    #[test]
+    fn test_malus_source_collision_same_penalty() {
+        let mut turn = RubberState::with_rubber("p1", 2.0);
+        let mut hit = RubberState::with_rubber("p2", 2.0);
+
+        let turn_malus = apply_malus(&mut turn, 0.5, 0.5);
+        let hit_malus = apply_malus_from(&mut hit, 0.5, 0.5, MalusSource::Collision);
+
+        assert_eq!(hit.last_malus_source, Some(MalusSource::Collision));
+        assert_eq!(turn_malus, hit_malus);
+        assert_eq!(turn.malus_timer, hit.malus_timer);
+
+        reset_rubber(&mut hit);
+        assert_eq!(hit.last_malus_source, None);
+    }
+
+    #[test]
+    fn test_death_radius_with_rubber_max_effectiveness() {
+        assert!((death_radius_with_rubber(2.0, 1.0, 0.5) - 1.5).abs() < EPS);
+        // Effectiveness above 1 is clamped
+        assert!((death_radius_with_rubber(2.0, 3.0, 0.5) - 1.5).abs() < EPS);
+        assert_eq!(death_radius_with_rubber(0.2, 1.0, 0.5), 0.0);
+    }
+
+    #[test]
+    fn test_death_radius_with_rubber_zero_effectiveness() {
+        assert_eq!(death_radius_with_rubber(2.0, 0.0, 0.5), 2.0);
+    }
+
+    #[test]
+    fn test_rubber_is_active_threshold_boundary() {
+        // Effectiveness crosses 0.5 at rubber = 0.1 + 0.5 * 4.9 = 2.55
+        assert!(!rubber_is_active(&RubberState::with_rubber("p1", 2.5)));
+        assert!(rubber_is_active(&RubberState::with_rubber("p1", 2.6)));
+        assert!(!rubber_is_active(&RubberState::new("p1")));
+    }
+
+    #[test]
+    fn test_speed_boost_only_when_active() {
+        let below = RubberState::with_rubber("p1", 2.5);
+        let above = RubberState::with_rubber("p1", 2.6);
+
+        assert_eq!(calculate_speed_modifier(&below, 40.0), 40.0);
+        assert!(calculate_speed_modifier(&above, 40.0) > 40.0);
+    }
+
+    #[test]
+    fn test_active_effectiveness_skips_inactive() {
+        let states = [
+            RubberState::with_rubber("behind", 4.0),
+            RubberState::with_rubber("leader", 1.0),
+        ];
+        let map = active_effectiveness(&states);
+
+        assert_eq!(map.len(), 1);
+        assert!(map["behind"] >= RUBBER_CONFIG.effectiveness_threshold);
+    }
+
+    #[test]
+    fn test_catchup_tier_boundaries() {
+        let [low, mid, high] = RUBBER_CONFIG.catchup_thresholds;
+
+        assert_eq!(catchup_tier(0.0), 0);
+        assert_eq!(catchup_tier(low - 0.01), 0);
+        assert_eq!(catchup_tier(low), 1);
+        assert_eq!(catchup_tier(mid - 0.01), 1);
+        assert_eq!(catchup_tier(mid), 2);
+        assert_eq!(catchup_tier(high - 0.01), 2);
+        assert_eq!(catchup_tier(high), 3);
+        assert_eq!(catchup_tier(1.0), 3);
+    }
+
+    #[test]
+    fn test_catchup_tier_custom_thresholds() {
+        let thresholds = [0.1, 0.2, 0.3];
+
+        assert_eq!(catchup_tier_with(0.05, &thresholds), 0);
+        assert_eq!(catchup_tier_with(0.15, &thresholds), 1);
+        assert_eq!(catchup_tier_with(0.25, &thresholds), 2);
+        assert_eq!(catchup_tier_with(0.5, &thresholds), 3);
+    }
+
+    #[test]
+    fn test_catchup_tier_for_position() {
+        let thresholds = RUBBER_CONFIG.catchup_thresholds;
+
+        assert_eq!(catchup_tier_for_position(1.0, 4, 4, &thresholds), 3);
+        assert_eq!(catchup_tier_for_position(1.0, 1, 4, &thresholds), 1);
+        assert_eq!(catchup_tier_for_position(1.0, 1, 1, &thresholds), 3);
+        assert_eq!(catchup_tier_for_position(1.0, 0, 4, &thresholds), 3);
+    }
 }
@@ -5,9 +5,9 @@
 //! to test the public API of the crate.
 
 use cyber_cycles_db::{
-    GlobalConfig, GameState, Player, Vec2,
+    CountdownPhase, GamePhase, GlobalConfig, GameState, Player, TimeoutResolution, Vec2,
 };
-use spacetimedb::Identity;
+use spacetimedb::{Identity, Timestamp};
 
 // ============================================================================
 // Test Fixtures
@@ -272,7 +272,7 @@ mod test_check_round_start {
 // ============================================================================
 
 mod test_tables {
-    use crate::{GlobalConfig, GameState, Player, Vec2, admin_identity, test_identity};
+    use crate::{CountdownPhase, GamePhase, GlobalConfig, GameState, Player, TimeoutResolution, Timestamp, Vec2, admin_identity, test_identity};
 
     /// Test GlobalConfig table structure
     #[test]
@@ -286,6 +286,25 @@ mod test_tables {
             max_trail_length: 200.0,
             slipstream_mode: "tail_only".to_string(),
             turn_speed: 3.0,
+            lives_mode: false,
+            starting_lives: 3,
+            respawn_delay: 3.0,
+            ai_fill_count: 6,
+            mid_round_spectate: true,
+            wrap_arena: false,
+            spawn_jitter: 0.0,
+            debug_queries: false,
+            celebration_duration: 3.0,
+            collision_caps: cyber_cycles_db::physics::collision::collision_capabilities(),
+            round_time_limit: 0.0,
+            timeout_resolution: TimeoutResolution::LongestTrail,
+            takedown_points: 0,
+            sync_min_interval_ms: 0,
+            health_mode: false,
+            overtime_after: 0.0,
+            overtime_speed_rate: 0.02,
+            shuffle_spawns: false,
+            dead_trails_persist: false,
         };
     }
 
@@ -295,6 +314,7 @@ mod test_tables {
         // TODO: Verify Player has all required fields
         let _player = Player {
             id: "p1".to_string(),
+            name: "p1".to_string(),
             owner_id: test_identity(),
             is_ai: true,
             personality: "aggressive".to_string(),
@@ -310,6 +330,26 @@ mod test_tables {
             alive: true,
             ready: true,
             turn_points_json: "[]".to_string(),
+            lives: 3,
+            respawn_timer: 0.0,
+            respawn_at: Timestamp::UNIX_EPOCH,
+            trail_armed: true,
+            rubber: 1.0,
+            immune_to: Vec::new(),
+            last_sync_at: None,
+            health: 100.0,
+            difficulty: 3,
+            reaction_delay: 0.19,
+            catchup_level: 0,
+            last_hit: String::new(),
+            last_hit_at: Timestamp::UNIX_EPOCH,
+            spawn_x: 0.0,
+            spawn_z: 0.0,
+            malus: 0.0,
+            malus_timer: 0.0,
+            malus_source: String::new(),
+            boost_energy: 100.0,
+            boost_cooldown: 0.0,
         };
     }
 
@@ -322,8 +362,17 @@ mod test_tables {
             winner_id: String::new(),
             round_active: false,
             countdown: 3,
+            phase: CountdownPhase::Ready,
             player_count: 6,
             alive_count: 6,
+            round_number: 0,
+            round_time: Timestamp::UNIX_EPOCH,
+            state_hash: 0,
+            game_phase: GamePhase::Warmup,
+            death_order: Vec::new(),
+            round_ended_at: Timestamp::UNIX_EPOCH,
+            post_round_hold: 0.0,
+            speed_scale: 1.0,
         };
     }
 